@@ -0,0 +1,80 @@
+//! Benchmarks of parsing and both parts of every day, run against puzzle
+//! inputs in `dayN/files/input.txt`. Days without an input are skipped.
+//!
+//! ```text
+//! cargo bench -p aoc -- --save-baseline before
+//! cargo bench -p aoc -- --baseline before day05
+//! ```
+
+use std::{fs, hint::black_box, path::Path};
+
+use common::{input::DEFAULT_PATH, solution::Solution};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn read_input(number: u8) -> Option<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{}", number))
+        .join(DEFAULT_PATH);
+
+    match fs::read_to_string(&path) {
+        Ok(input) => Some(input),
+        Err(_) => {
+            eprintln!("day {:>2}: no input at {}, skipped", number, path.display());
+            None
+        }
+    }
+}
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let Some(text) = read_input(S::DAY) else {
+        return;
+    };
+
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+    // Some solutions take seconds, the minimal sample keeps the whole suite
+    // runnable.
+    group.sample_size(10);
+
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&text)).unwrap()));
+
+    let input = S::parse(&text).unwrap();
+
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&input)).unwrap()));
+    if S::PARTS >= 2 {
+        group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&input)).unwrap()));
+    }
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c);
+    bench_day::<day2::Day2>(c);
+    bench_day::<day3::Day3>(c);
+    bench_day::<day4::Day4>(c);
+    bench_day::<day5::Day5>(c);
+    bench_day::<day6::Day6>(c);
+    bench_day::<day7::Day7>(c);
+    bench_day::<day8::Day8>(c);
+    bench_day::<day9::Day9>(c);
+    bench_day::<day10::Day10>(c);
+    bench_day::<day11::Day11>(c);
+    bench_day::<day12::Day12>(c);
+    bench_day::<day13::Day13>(c);
+    bench_day::<day14::Day14>(c);
+    bench_day::<day15::Day15>(c);
+    bench_day::<day16::Day16>(c);
+    bench_day::<day17::Day17>(c);
+    bench_day::<day18::Day18>(c);
+    bench_day::<day19::Day19>(c);
+    bench_day::<day20::Day20>(c);
+    bench_day::<day21::Day21>(c);
+    bench_day::<day22::Day22>(c);
+    bench_day::<day23::Day23>(c);
+    bench_day::<day24::Day24>(c);
+    bench_day::<day25::Day25>(c);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,165 @@
+//! Known-good answers, stored per input hash, day and part so that answers
+//! for different inputs of the same day don't clash.
+
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path};
+
+use common::{
+    error::{Error, Result},
+    report::Record,
+};
+use serde::{Deserialize, Serialize};
+
+/// Answers file in the workspace root.
+pub const DEFAULT_PATH: &str = "answers.json";
+
+/// Result of comparing an answer with the known one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// No answer is known for this input yet.
+    New,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Status::New => write!(f, "NEW"),
+        }
+    }
+}
+
+/// Line of the answers file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    input_hash: String,
+    day: u8,
+    part: u8,
+    answer: String,
+}
+
+type Key = (String, u8, u8);
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    known: BTreeMap<Key, String>,
+}
+
+impl Answers {
+    /// Reads answers from `path`, a missing file has no answers.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(err) => return Err(Error::new(format!("{}: {}", path.display(), err))),
+        };
+
+        let entries: Vec<Entry> = serde_json::from_str(&text)
+            .map_err(|err| Error::new(format!("{}: {}", path.display(), err)))?;
+
+        Ok(Answers {
+            known: entries
+                .into_iter()
+                .map(|e| ((e.input_hash, e.day, e.part), e.answer))
+                .collect(),
+        })
+    }
+
+    /// Writes all answers to `path`, sorted by input hash, day and part to
+    /// keep diffs of the file small.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let entries: Vec<Entry> = self
+            .known
+            .iter()
+            .map(|((input_hash, day, part), answer)| Entry {
+                input_hash: input_hash.clone(),
+                day: *day,
+                part: *part,
+                answer: answer.clone(),
+            })
+            .collect();
+
+        let text = serde_json::to_string_pretty(&entries).expect("answers are always serializable");
+        fs::write(path, text + "\n")
+            .map_err(|err| Error::new(format!("{}: {}", path.display(), err)))
+    }
+
+    pub fn check(&self, record: &Record) -> Status {
+        match self.known.get(&key(record)) {
+            Some(expected) if *expected == record.answer => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.clone(),
+            },
+            None => Status::New,
+        }
+    }
+
+    /// Remembers the answer of `record` as the known-good one.
+    pub fn record(&mut self, record: &Record) {
+        self.known.insert(key(record), record.answer.clone());
+    }
+}
+
+fn key(record: &Record) -> Key {
+    (record.input_hash.clone(), record.day, record.part)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use common::report::hash;
+
+    use super::*;
+
+    fn record(day: u8, part: u8, input: &str, answer: &str) -> Record {
+        Record {
+            day,
+            part,
+            answer: answer.to_string(),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+            input_hash: hash(input),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        assert_eq!(answers.check(&record(6, 1, "a", "288")), Status::New);
+
+        answers.record(&record(6, 1, "a", "288"));
+        assert_eq!(answers.check(&record(6, 1, "a", "288")), Status::Pass);
+        assert_eq!(
+            answers.check(&record(6, 1, "a", "289")),
+            Status::Fail {
+                expected: "288".to_string()
+            }
+        );
+        // Same day with another input or another part is unknown.
+        assert_eq!(answers.check(&record(6, 1, "b", "288")), Status::New);
+        assert_eq!(answers.check(&record(6, 2, "a", "288")), Status::New);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.json", std::process::id()));
+
+        let mut answers = Answers::default();
+        answers.record(&record(6, 1, "a", "288"));
+        answers.record(&record(6, 2, "a", "71503"));
+        answers.save(&path).unwrap();
+
+        let loaded = Answers::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.known, answers.known);
+        assert!(Answers::load(&path).unwrap().known.is_empty());
+    }
+}
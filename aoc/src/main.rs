@@ -0,0 +1,329 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+    time::Duration,
+};
+
+use answers::{Answers, Status};
+use clap::{builder::RangedU64ValueParser, Parser, Subcommand};
+use common::{
+    generate::Rng,
+    input::{Source, DEFAULT_PATH},
+    memory, parallel,
+    report::{Format, Record},
+    trace::Trace,
+};
+use serde::Serialize;
+
+mod answers;
+mod days;
+mod replay;
+
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code 2023 solutions runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run solvers of one day or of all days.
+    Run(RunArgs),
+    /// Play a trace recorded by a day binary with --trace in the terminal or
+    /// export it as GIF.
+    Replay(ReplayArgs),
+    /// Print a random puzzle input of a day, to stress solvers with inputs
+    /// larger than the real one.
+    Generate(GenerateArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct RunArgs {
+    /// Day number (1-25) or "all".
+    day: Selection,
+
+    /// Run only the given part.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input file, "-" to read from stdin. Defaults to the input
+    /// file in the day directory, can be used only with a single day.
+    #[arg(short, long)]
+    input: Option<Source>,
+
+    /// Output format of answers.
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+
+    /// File with known-good answers to check against. Defaults to
+    /// answers.json in the workspace root.
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Save answers which aren't known yet.
+    #[arg(long)]
+    record: bool,
+
+    /// Replace known answers which differ from the new ones when recording.
+    #[arg(long, requires = "record")]
+    overwrite: bool,
+
+    /// Number of threads of solvers working in parallel, all cores by
+    /// default.
+    #[arg(long, value_name = "N", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    threads: Option<usize>,
+
+    /// Report the number of heap allocations and the peak heap usage of each
+    /// part.
+    #[arg(long)]
+    memory: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct ReplayArgs {
+    /// Trace file.
+    trace: PathBuf,
+
+    /// Delay between frames in milliseconds.
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+
+    /// Write an animated GIF to the file instead of playing in the terminal.
+    #[arg(long, value_name = "FILE")]
+    gif: Option<PathBuf>,
+
+    /// Size of a cell in GIF pixels.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    scale: u16,
+}
+
+#[derive(Debug, clap::Args)]
+struct GenerateArgs {
+    /// Day number (1-25).
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Size of the input, what it counts depends on the day: lines, rows of
+    /// a map, bricks...
+    #[arg(long, default_value_t = 100)]
+    size: usize,
+
+    /// Seed of the random generator, the same seed and size give the same
+    /// input.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Selection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Selection::All);
+        }
+
+        match s.parse::<u8>() {
+            Ok(n) if (1..=25).contains(&n) => Ok(Selection::Day(n)),
+            _ => Err(format!(
+                "expected day number 1-25 or \"all\", got \"{}\"",
+                s
+            )),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Replay(args) => replay(args),
+        Command::Generate(args) => generate(args),
+    }
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let RunArgs {
+        day: selection,
+        part,
+        input,
+        format,
+        answers: answers_path,
+        record,
+        overwrite,
+        threads,
+        memory,
+    } = args;
+
+    if let Some(threads) = threads {
+        if let Err(err) = parallel::set_threads(threads) {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+    if memory {
+        memory::enable();
+    }
+
+    if let (Selection::All, Some(_)) = (selection, &input) {
+        eprintln!("--input can't be used with all days");
+        return ExitCode::FAILURE;
+    }
+
+    let answers_path = answers_path.unwrap_or_else(|| workspace_dir().join(answers::DEFAULT_PATH));
+    let mut answers = match Answers::load(&answers_path) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let days: Vec<days::Day> = days::all()
+        .into_iter()
+        .filter(|d| match selection {
+            Selection::All => true,
+            Selection::Day(n) => d.number == n,
+        })
+        .collect();
+
+    let parts: Vec<u8> = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+
+    let mut total = Duration::ZERO;
+    let mut failed = false;
+
+    for day in &days {
+        let source = input
+            .clone()
+            .unwrap_or_else(|| Source::File(day_dir(day.number).join(DEFAULT_PATH)));
+
+        let data = match source.read() {
+            Ok(data) => data,
+            Err(err) => {
+                eprintln!("day {:>2}: {}: {}", day.number, source, err);
+                failed = true;
+                continue;
+            }
+        };
+
+        for &p in &parts {
+            if p > day.parts {
+                continue;
+            }
+
+            match (day.solve)(p, &data) {
+                Ok(res) => {
+                    total += res.total_time();
+
+                    let status = answers.check(&res);
+                    let save = match status {
+                        Status::Pass => false,
+                        Status::Fail { .. } => {
+                            failed = true;
+                            overwrite
+                        }
+                        Status::New => record,
+                    };
+                    if save {
+                        answers.record(&res);
+                    }
+
+                    print(&res, &status, format);
+                }
+                Err(err) => {
+                    eprintln!("day {:>2} part {}: error: {}", day.number, p, err);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    // JSON output is one record per line, the total is easy to sum up.
+    if let (Selection::All, Format::Text) = (selection, format) {
+        println!("total: {:.3?}", total);
+    }
+
+    if record {
+        if let Err(err) = answers.save(&answers_path) {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn replay(args: ReplayArgs) -> ExitCode {
+    let delay = Duration::from_millis(args.delay);
+
+    let res = Trace::load(&args.trace).and_then(|trace| match &args.gif {
+        Some(path) => replay::export_gif(&trace, path, delay, args.scale),
+        None => replay::play(&trace, delay),
+    });
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn generate(args: GenerateArgs) -> ExitCode {
+    match days::all().into_iter().find(|d| d.number == args.day) {
+        Some(day) => {
+            print!("{}", (day.generate)(args.size, &mut Rng::new(args.seed)));
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("day {} has no solution", args.day);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn print(record: &Record, status: &Status, format: Format) {
+    match format {
+        Format::Text => println!("{} {}", record, status),
+        Format::Json => {
+            #[derive(Serialize)]
+            struct Checked<'a> {
+                #[serde(flatten)]
+                record: &'a Record,
+                #[serde(flatten)]
+                status: &'a Status,
+            }
+
+            let line = serde_json::to_string(&Checked { record, status })
+                .expect("record is always serializable");
+            println!("{}", line);
+        }
+    }
+}
+
+fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn day_dir(number: u8) -> PathBuf {
+    workspace_dir().join(format!("day{}", number))
+}
@@ -0,0 +1,186 @@
+//! Playback of recorded traces frame by frame in a terminal and export of
+//! them as animated GIF.
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use common::{
+    error::{Error, Result},
+    render::{Canvas, Format, Style},
+    trace::Trace,
+};
+use gif::{Encoder, Repeat};
+
+/// Clears the terminal and moves the cursor to its top left corner.
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Draws frames over each other in the terminal with `delay` between them.
+pub fn play(trace: &Trace, delay: Duration) -> Result<()> {
+    let mut out = io::stdout().lock();
+
+    for (i, frame) in trace.frames().enumerate() {
+        writeln!(
+            out,
+            "{}{}day {} frame {}/{}",
+            CLEAR,
+            frame.render(Format::Ansi),
+            trace.day,
+            i + 1,
+            trace.len()
+        )?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+
+    Ok(())
+}
+
+/// Palette indices of GIF pixels.
+const BACKGROUND: u8 = 0;
+const WALL: u8 = 1;
+const OTHER: u8 = 2;
+const PATH: u8 = 3;
+const VISITED: u8 = 4;
+const HIGHLIGHT: u8 = 5;
+
+/// RGB colours in the order of palette indices.
+fn palette() -> Vec<u8> {
+    let mut res = vec![0xff, 0xff, 0xff, 0x3c, 0x3c, 0x3c, 0xaa, 0xaa, 0xaa];
+    for style in [Style::Path, Style::Visited, Style::Highlight] {
+        res.extend(style.rgb());
+    }
+    res
+}
+
+/// Palette index of a cell, styles take precedence over characters.
+fn color(ch: char, style: Option<Style>) -> u8 {
+    match (style, ch) {
+        (Some(Style::Path), _) => PATH,
+        (Some(Style::Visited), _) => VISITED,
+        (Some(Style::Highlight), _) => HIGHLIGHT,
+        (None, '.' | ' ') => BACKGROUND,
+        (None, '#') => WALL,
+        (None, _) => OTHER,
+    }
+}
+
+/// Writes all frames as an endlessly looping GIF, each cell is a square of
+/// `scale` pixels. Frames smaller than the largest one are padded with the
+/// background.
+pub fn export_gif(trace: &Trace, path: &Path, delay: Duration, scale: u16) -> Result<()> {
+    let rows = trace.frames().map(|f| f.rows()).max().unwrap_or(0);
+    let cols = trace.frames().map(|f| f.cols()).max().unwrap_or(0);
+
+    let size = |cells: usize| {
+        u16::try_from(cells)
+            .ok()
+            .and_then(|n| n.checked_mul(scale))
+            .ok_or_else(|| Error::new(format!("frames are too large for scale {}", scale)))
+    };
+    let (width, height) = (size(cols)?, size(rows)?);
+
+    let file =
+        File::create(path).map_err(|err| Error::new(format!("{}: {}", path.display(), err)))?;
+    let mut encoder = Encoder::new(file, width, height, &palette()).map_err(gif_error)?;
+    encoder.set_repeat(Repeat::Infinite).map_err(gif_error)?;
+
+    // GIF delays are in hundredths of a second.
+    let delay = u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX);
+
+    for canvas in trace.frames() {
+        let frame = gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: pixels(&canvas, rows, cols, scale as usize).into(),
+            ..Default::default()
+        };
+        encoder.write_frame(&frame).map_err(gif_error)?;
+    }
+
+    Ok(())
+}
+
+/// Palette indices of the pixels of a frame of `rows` x `cols` cells.
+fn pixels(canvas: &Canvas, rows: usize, cols: usize, scale: usize) -> Vec<u8> {
+    let mut res = Vec::with_capacity(rows * cols * scale * scale);
+
+    for row in 0..rows {
+        let line: Vec<u8> = (0..cols)
+            .flat_map(|col| {
+                let c = match canvas.get((row, col)) {
+                    Some(ch) => color(ch, canvas.style((row, col))),
+                    None => BACKGROUND,
+                };
+                std::iter::repeat_n(c, scale)
+            })
+            .collect();
+
+        for _ in 0..scale {
+            res.extend(&line);
+        }
+    }
+
+    res
+}
+
+fn gif_error(err: gif::EncodingError) -> Error {
+    Error::new(format!("GIF: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace() -> Trace {
+        let mut trace = Trace::new(16);
+
+        let mut frame = Canvas::from_text("#.\n.x\n");
+        trace.record(frame.clone());
+        frame.draw((0, 1), '>', Style::Path);
+        trace.record(frame);
+
+        trace
+    }
+
+    #[test]
+    fn test_pixels() {
+        let frame = trace().frames().last().unwrap();
+
+        assert_eq!(
+            pixels(&frame, 2, 3, 2),
+            vec![
+                1, 1, 3, 3, 0, 0, //
+                1, 1, 3, 3, 0, 0, //
+                0, 0, 2, 2, 0, 0, //
+                0, 0, 2, 2, 0, 0, //
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_gif() {
+        let path = std::env::temp_dir().join(format!("aoc-replay-{}.gif", std::process::id()));
+        export_gif(&trace(), &path, Duration::from_millis(200), 3).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 6));
+
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.buffer.to_vec()));
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].0, 20);
+        assert_eq!(frames[1].1[..6], [1, 1, 1, 3, 3, 3]);
+    }
+}
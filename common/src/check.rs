@@ -0,0 +1,64 @@
+//! Cross-checks of optimized solvers against naive reference ones, which are
+//! often too slow for the real input but simple enough to trust. Generated
+//! inputs keep them fast.
+
+use std::fmt::Display;
+
+/// Answers of a reference solver and an optimized one to the same part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub part: u8,
+    /// Solvers compared, such as "bitmask vs memoized".
+    pub name: String,
+    pub reference: String,
+    pub optimized: String,
+}
+
+impl Check {
+    pub fn new(part: u8, name: &str, reference: impl Display, optimized: impl Display) -> Self {
+        Check {
+            part,
+            name: name.to_string(),
+            reference: reference.to_string(),
+            optimized: optimized.to_string(),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.reference == self.optimized
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "part {} {}: ", self.part, self.name)?;
+        if self.passed() {
+            write!(f, "{} ok", self.optimized)
+        } else {
+            write!(
+                f,
+                "MISMATCH reference {}, optimized {}",
+                self.reference, self.optimized
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let check = Check::new(1, "bitmask vs memoized", 21, 21_u64);
+        assert!(check.passed());
+        assert_eq!(check.to_string(), "part 1 bitmask vs memoized: 21 ok");
+
+        let check = Check::new(2, "flood vs intervals", 62, 61);
+        assert!(!check.passed());
+        assert_eq!(
+            check.to_string(),
+            "part 2 flood vs intervals: MISMATCH reference 62, optimized 61"
+        );
+    }
+}
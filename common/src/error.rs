@@ -0,0 +1,270 @@
+//! Error type shared by all days.
+//!
+//! Errors remember which line of the input and which part of it caused them
+//! and are printed as a diagnostic with the offending text underlined:
+//!
+//! ```text
+//! invalid card
+//!  --> 3:5
+//!   |
+//! 3 | 32T3X 765
+//!   |     ^
+//! ```
+
+use std::{
+    fmt::{Debug, Display},
+    io, num,
+    str::FromStr,
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub struct Error {
+    message: String,
+    text: Option<Span>,
+    location: Option<Location>,
+}
+
+/// Offending text and its address, used to find exact column when the text
+/// is a slice of the input line.
+struct Span {
+    text: String,
+    addr: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// Line number, starting from 1.
+    pub line: usize,
+    /// Column in characters, starting from 1.
+    pub column: usize,
+    /// Length of the underlined text in characters.
+    pub len: usize,
+    /// Text of the whole line.
+    pub source: String,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Error {
+            message: message.into(),
+            text: None,
+            location: None,
+        }
+    }
+
+    /// Error about `text` which can't be interpreted as `what`.
+    pub fn invalid(what: &str, text: &str) -> Self {
+        Error::new(format!("invalid {}", what)).with_text(text)
+    }
+
+    /// Remembers offending text, preferably a slice of the input line.
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = Some(Span {
+            text: text.to_string(),
+            addr: text.as_ptr() as usize,
+        });
+        self
+    }
+
+    /// Attaches location in the input, `index` is zero-based index of `line`.
+    /// The location is kept if it was already set.
+    pub fn at_line(self, index: usize, line: &str) -> Self {
+        if self.location.is_some() {
+            return self;
+        }
+
+        let (offset, len) = match &self.text {
+            Some(span) => {
+                let start = line.as_ptr() as usize;
+                let offset = if span.addr >= start
+                    && span.addr + span.text.len() <= start + line.len()
+                    && line.is_char_boundary(span.addr - start)
+                {
+                    Some(span.addr - start)
+                } else if span.text.is_empty() {
+                    None
+                } else {
+                    line.find(&span.text)
+                };
+
+                match offset {
+                    Some(offset) => (offset, span.text.chars().count()),
+                    None => (0, line.chars().count()),
+                }
+            }
+            None => (0, line.chars().count()),
+        };
+
+        let column = line[..offset].chars().count();
+
+        self.at(index, column, len, line)
+    }
+
+    /// Attaches location in the input with explicit zero-based `column` and
+    /// length of the underlined text.
+    pub fn at(mut self, index: usize, column: usize, len: usize, line: &str) -> Self {
+        self.location = Some(Location {
+            line: index + 1,
+            column: column + 1,
+            len: len.max(1),
+            source: line.to_string(),
+        });
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_ref().map(|s| s.text.as_str())
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.location, self.text()) {
+            (None, Some(text)) => write!(f, "{}: {:?}", self.message, text),
+            (None, None) => write!(f, "{}", self.message),
+            (Some(loc), _) => {
+                let number = loc.line.to_string();
+                let pad = " ".repeat(number.len());
+
+                writeln!(f, "{}", self.message)?;
+                writeln!(f, "{}--> {}:{}", pad, loc.line, loc.column)?;
+                writeln!(f, "{} |", pad)?;
+                writeln!(f, "{} | {}", number, loc.source)?;
+                write!(
+                    f,
+                    "{} | {}{}",
+                    pad,
+                    " ".repeat(loc.column - 1),
+                    "^".repeat(loc.len)
+                )
+            }
+        }
+    }
+}
+
+/// Same as `Display`, so that errors returned from `main` are readable.
+impl Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Errors are equal when they have the same message, text and location.
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message
+            && self.text() == other.text()
+            && self.location == other.location
+    }
+}
+
+impl Eq for Error {}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::new(value.to_string())
+    }
+}
+
+impl From<num::ParseIntError> for Error {
+    fn from(value: num::ParseIntError) -> Self {
+        Error::new(format!("invalid number: {}", value))
+    }
+}
+
+impl From<&str> for Error {
+    fn from(value: &str) -> Self {
+        Error::new(value)
+    }
+}
+
+impl From<String> for Error {
+    fn from(value: String) -> Self {
+        Error::new(value)
+    }
+}
+
+/// Attaching input location to any result which can be converted to `Result`.
+pub trait Context<T> {
+    fn at_line(self, index: usize, line: &str) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> Context<T> for std::result::Result<T, E> {
+    fn at_line(self, index: usize, line: &str) -> Result<T> {
+        self.map_err(|e| e.into().at_line(index, line))
+    }
+}
+
+/// Parses `s` with `FromStr`, on failure the error refers to `s` as invalid `what`.
+pub fn parse<T: FromStr>(s: &str, what: &str) -> Result<T> {
+    s.parse().map_err(|_| Error::invalid(what, s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_line() {
+        let line = "32T3X 765";
+        let err = Error::invalid("card", &line[4..5]).at_line(2, line);
+
+        assert_eq!(
+            err.location(),
+            Some(&Location {
+                line: 3,
+                column: 5,
+                len: 1,
+                source: line.to_string()
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "invalid card\n --> 3:5\n  |\n3 | 32T3X 765\n  |     ^"
+        );
+    }
+
+    #[test]
+    fn test_at_line_copied_text() {
+        let line = "1 2 x 2";
+        let err: Error = parse::<u32>("x", "number").unwrap_err().at_line(0, line);
+
+        assert_eq!(err.location().unwrap().column, 5);
+
+        // Without a known part the whole line is underlined.
+        let err = Error::new("bad line").at_line(0, line);
+        assert_eq!(err.location().unwrap().column, 1);
+        assert_eq!(err.location().unwrap().len, 7);
+    }
+
+    #[test]
+    fn test_context() {
+        let res: std::result::Result<u32, &str> = Err("no way");
+        let err = res.at_line(9, "abc").unwrap_err();
+
+        assert_eq!(err.message(), "no way");
+        assert_eq!(err.location().unwrap().line, 10);
+        // Location is set only once.
+        assert_eq!(err.at_line(0, "def").location().unwrap().line, 10);
+    }
+
+    #[test]
+    fn test_display_without_location() {
+        assert_eq!(
+            Error::invalid("card", "X").to_string(),
+            "invalid card: \"X\""
+        );
+        assert_eq!(Error::new("empty input").to_string(), "empty input");
+    }
+}
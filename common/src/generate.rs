@@ -0,0 +1,205 @@
+//! Random generation of synthetic puzzle inputs of arbitrary size, used to
+//! stress solvers beyond the size of the real inputs.
+//!
+//! The generator is a small SplitMix64 instead of an external crate, so the
+//! same seed keeps producing the same input across dependency updates.
+
+use std::ops::Range;
+
+/// Deterministic pseudo-random number generator.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Number in `0..n`, `n` must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Number in `range`, which must not be empty.
+    pub fn usize(&mut self, range: Range<usize>) -> usize {
+        assert!(range.start < range.end, "empty range");
+        range.start + self.below((range.end - range.start) as u64) as usize
+    }
+
+    /// Number in `range`, which must not be empty.
+    pub fn i64(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "empty range");
+        let len = range.end.abs_diff(range.start);
+        range.start.wrapping_add(self.below(len) as i64)
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// Random element of `items`, which must not be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.usize(0..items.len())]
+    }
+
+    /// Puts `items` in random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.usize(0..i + 1));
+        }
+    }
+}
+
+/// Random shape of cells in a grid of `rows` x `cols`, grown from a random
+/// cell by adding cells which create neither holes nor cells touching only by
+/// corners. Its outline is a single loop which never touches itself.
+pub fn polyomino(rng: &mut Rng, rows: usize, cols: usize) -> Vec<Vec<bool>> {
+    // Neighbours of a cell in clockwise order, starting above it.
+    const AROUND: [(isize, isize); 8] = [
+        (-1, 0),
+        (-1, 1),
+        (0, 1),
+        (1, 1),
+        (1, 0),
+        (1, -1),
+        (0, -1),
+        (-1, -1),
+    ];
+
+    let mut shape = vec![vec![false; cols]; rows];
+    if rows == 0 || cols == 0 {
+        return shape;
+    }
+    shape[rng.usize(0..rows)][rng.usize(0..cols)] = true;
+
+    let inside = |shape: &[Vec<bool>], row: isize, col: isize| {
+        row >= 0
+            && col >= 0
+            && shape
+                .get(row as usize)
+                .and_then(|r| r.get(col as usize))
+                .copied()
+                .unwrap_or(false)
+    };
+
+    for _ in 0..rows * cols * 2 {
+        let (row, col) = (rng.usize(0..rows), rng.usize(0..cols));
+        if shape[row][col] {
+            continue;
+        }
+        let around: Vec<bool> = AROUND
+            .iter()
+            .map(|(dr, dc)| inside(&shape, row as isize + dr, col as isize + dc))
+            .collect();
+        // Diagonal neighbours must touch the cell through a side neighbour.
+        let pinched = (1..8)
+            .step_by(2)
+            .any(|i| around[i] && !around[i - 1] && !around[(i + 1) % 8]);
+        // A single run of neighbours, otherwise the cell closes a hole.
+        let runs = (0..8)
+            .filter(|&i| around[i] && !around[(i + 7) % 8])
+            .count();
+        let touching = (0..8).step_by(2).any(|i| around[i]);
+        if touching && !pinched && runs == 1 {
+            shape[row][col] = true;
+        }
+    }
+
+    shape
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+        // First SplitMix64 output for seed 0.
+        assert_eq!(numbers(0)[0], 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..1000 {
+            assert!((3..10).contains(&rng.usize(3..10)));
+            assert!((-5..5).contains(&rng.i64(-5..5)));
+            assert!(rng.below(1) == 0);
+        }
+        assert_eq!(rng.i64(i64::MIN..i64::MIN + 1), i64::MIN);
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_polyomino() {
+        for seed in 0..20 {
+            let shape = polyomino(&mut Rng::new(seed), 8, 12);
+            let cell = |r: isize, c: isize| {
+                r >= 0 && c >= 0 && r < 8 && c < 12 && shape[r as usize][c as usize]
+            };
+
+            // Every corner of cells has zero or two sides on the outline, so
+            // the outline never touches itself.
+            for r in 0..=8 {
+                for c in 0..=12 {
+                    let sides = [
+                        cell(r - 1, c - 1) != cell(r - 1, c),
+                        cell(r, c - 1) != cell(r, c),
+                        cell(r - 1, c - 1) != cell(r, c - 1),
+                        cell(r - 1, c) != cell(r, c),
+                    ];
+                    assert!([0, 2].contains(&sides.iter().filter(|&&s| s).count()));
+                }
+            }
+
+            // Cells outside are all reachable from the border, so there are
+            // no holes.
+            let mut outside = vec![vec![false; 14]; 10];
+            let mut stack = vec![(0, 0)];
+            while let Some((r, c)) = stack.pop() {
+                if outside[r][c] || cell(r as isize - 1, c as isize - 1) {
+                    continue;
+                }
+                outside[r][c] = true;
+                for (dr, dc) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                    let (nr, nc) = (r as isize + dr, c as isize + dc);
+                    if (0..10).contains(&nr) && (0..14).contains(&nc) {
+                        stack.push((nr as usize, nc as usize));
+                    }
+                }
+            }
+            let holes = (1..9)
+                .flat_map(|r| (1..13).map(move |c| (r, c)))
+                .filter(|&(r, c)| !outside[r][c] && !shape[r - 1][c - 1])
+                .count();
+            assert_eq!(holes, 0);
+        }
+    }
+}
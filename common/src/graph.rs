@@ -0,0 +1,317 @@
+//! Export of graph-shaped puzzle inputs to DOT and GraphML, so they can be
+//! laid out and explored with Graphviz, Gephi, yEd and similar tools.
+
+use std::collections::{BTreeSet, HashMap};
+
+use clap::ValueEnum;
+
+/// Output format of a graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Graphviz DOT language.
+    Dot,
+    /// GraphML XML.
+    Graphml,
+}
+
+/// Node with attributes such as `label` or `shape`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    id: String,
+    attrs: Vec<(String, String)>,
+}
+
+/// Edge with attributes such as `label` or `weight`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Edge {
+    from: String,
+    to: String,
+    attrs: Vec<(String, String)>,
+}
+
+/// Directed or undirected graph, nodes and edges keep their insertion order so
+/// the output is stable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    pub name: String,
+    pub directed: bool,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    /// Position of each node in `nodes`.
+    index: HashMap<String, usize>,
+}
+
+impl Graph {
+    pub fn directed(name: &str) -> Self {
+        Self::new(name, true)
+    }
+
+    pub fn undirected(name: &str) -> Self {
+        Self::new(name, false)
+    }
+
+    fn new(name: &str, directed: bool) -> Self {
+        Graph {
+            name: name.to_string(),
+            directed,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Adds a node or sets attributes of an existing one.
+    pub fn node(&mut self, id: &str, attrs: &[(&str, &str)]) {
+        let i = self.add(id);
+        let node = &mut self.nodes[i];
+        for &(key, value) in attrs {
+            match node.attrs.iter_mut().find(|(k, _)| k == key) {
+                Some((_, v)) => *v = value.to_string(),
+                None => node.attrs.push((key.to_string(), value.to_string())),
+            }
+        }
+    }
+
+    /// Adds an edge, along with its nodes if they are missing.
+    pub fn edge(&mut self, from: &str, to: &str, attrs: &[(&str, &str)]) {
+        self.add(from);
+        self.add(to);
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            attrs: attrs
+                .iter()
+                .map(|&(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        });
+    }
+
+    /// Position of node `id`, added without attributes if missing.
+    fn add(&mut self, id: &str) -> usize {
+        if let Some(&i) = self.index.get(id) {
+            return i;
+        }
+        self.nodes.push(Node {
+            id: id.to_string(),
+            attrs: Vec::new(),
+        });
+        self.index.insert(id.to_string(), self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Dot => self.to_dot(),
+            Format::Graphml => self.to_graphml(),
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut res = format!("{} {} {{\n", keyword, dot_id(&self.name));
+        for node in &self.nodes {
+            res.push_str(&format!(
+                "  {}{};\n",
+                dot_id(&node.id),
+                dot_attrs(&node.attrs)
+            ));
+        }
+        for edge in &self.edges {
+            res.push_str(&format!(
+                "  {} {} {}{};\n",
+                dot_id(&edge.from),
+                arrow,
+                dot_id(&edge.to),
+                dot_attrs(&edge.attrs)
+            ));
+        }
+        res.push_str("}\n");
+
+        res
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let node_keys: BTreeSet<&str> = self
+            .nodes
+            .iter()
+            .flat_map(|n| n.attrs.iter().map(|(k, _)| k.as_str()))
+            .collect();
+        let edge_keys: BTreeSet<&str> = self
+            .edges
+            .iter()
+            .flat_map(|e| e.attrs.iter().map(|(k, _)| k.as_str()))
+            .collect();
+
+        let mut res = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n"
+        ));
+        for (prefix, scope, keys) in [("n", "node", &node_keys), ("e", "edge", &edge_keys)] {
+            for key in keys {
+                res.push_str(&format!(
+                    "  <key id=\"{}_{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"string\"/>\n",
+                    prefix,
+                    xml_escape(key),
+                    scope,
+                    xml_escape(key)
+                ));
+            }
+        }
+
+        res.push_str(&format!(
+            "  <graph id=\"{}\" edgedefault=\"{}\">\n",
+            xml_escape(&self.name),
+            if self.directed {
+                "directed"
+            } else {
+                "undirected"
+            }
+        ));
+        for node in &self.nodes {
+            res.push_str(&format!("    <node id=\"{}\"", xml_escape(&node.id)));
+            res.push_str(&graphml_data("n", &node.attrs, "node"));
+        }
+        for edge in &self.edges {
+            res.push_str(&format!(
+                "    <edge source=\"{}\" target=\"{}\"",
+                xml_escape(&edge.from),
+                xml_escape(&edge.to)
+            ));
+            res.push_str(&graphml_data("e", &edge.attrs, "edge"));
+        }
+        res.push_str("  </graph>\n</graphml>\n");
+
+        res
+    }
+}
+
+/// Quoted DOT identifier.
+fn dot_id(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// DOT attribute list, empty without attributes.
+fn dot_attrs(attrs: &[(String, String)]) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+    let list: Vec<String> = attrs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, dot_id(v)))
+        .collect();
+    format!(" [{}]", list.join(", "))
+}
+
+/// Rest of a GraphML element after its opening attributes: self-closing
+/// without attributes, otherwise a `data` child for each.
+fn graphml_data(prefix: &str, attrs: &[(String, String)], tag: &str) -> String {
+    if attrs.is_empty() {
+        return "/>\n".to_string();
+    }
+    let mut res = String::from(">\n");
+    for (k, v) in attrs {
+        res.push_str(&format!(
+            "      <data key=\"{}_{}\">{}</data>\n",
+            prefix,
+            xml_escape(k),
+            xml_escape(v)
+        ));
+    }
+    res.push_str(&format!("    </{}>\n", tag));
+    res
+}
+
+fn xml_escape(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        let mut g = Graph::directed("g");
+        g.node("a", &[("shape", "box")]);
+        g.edge("a", "b", &[("label", "x<1")]);
+        g.edge("b", "a", &[]);
+        g.node("a", &[("shape", "circle"), ("label", "A \"1\"")]);
+        g
+    }
+
+    #[test]
+    fn test_graph() {
+        let g = graph();
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        assert_eq!(
+            graph().to_dot(),
+            concat!(
+                "digraph \"g\" {\n",
+                "  \"a\" [shape=\"circle\", label=\"A \\\"1\\\"\"];\n",
+                "  \"b\";\n",
+                "  \"a\" -> \"b\" [label=\"x<1\"];\n",
+                "  \"b\" -> \"a\";\n",
+                "}\n"
+            )
+        );
+
+        let mut g = Graph::undirected("u");
+        g.edge("a", "b", &[]);
+        assert_eq!(
+            g.render(Format::Dot),
+            "graph \"u\" {\n  \"a\";\n  \"b\";\n  \"a\" -- \"b\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_to_graphml() {
+        assert_eq!(
+            graph().render(Format::Graphml),
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+                "  <key id=\"n_label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+                "  <key id=\"n_shape\" for=\"node\" attr.name=\"shape\" attr.type=\"string\"/>\n",
+                "  <key id=\"e_label\" for=\"edge\" attr.name=\"label\" attr.type=\"string\"/>\n",
+                "  <graph id=\"g\" edgedefault=\"directed\">\n",
+                "    <node id=\"a\">\n",
+                "      <data key=\"n_shape\">circle</data>\n",
+                "      <data key=\"n_label\">A &quot;1&quot;</data>\n",
+                "    </node>\n",
+                "    <node id=\"b\"/>\n",
+                "    <edge source=\"a\" target=\"b\">\n",
+                "      <data key=\"e_label\">x&lt;1</data>\n",
+                "    </edge>\n",
+                "    <edge source=\"b\" target=\"a\"/>\n",
+                "  </graph>\n",
+                "</graphml>\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Puzzle input sources.
+
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use clap::{builder::RangedU64ValueParser, Parser};
+
+use crate::{graph, render, report::Format};
+
+/// Default location of puzzle input relative to a day directory.
+pub const DEFAULT_PATH: &str = "files/input.txt";
+
+/// Where puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Source::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        let mut buf = String::new();
+        self.reader()?.read_to_string(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::File(PathBuf::from(DEFAULT_PATH))
+    }
+}
+
+/// "-" stands for stdin, anything else is a file path.
+impl FromStr for Source {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(Source::Stdin),
+            path => Ok(Source::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "-"),
+        }
+    }
+}
+
+/// Command line arguments accepted by every day binary.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Puzzle input file, "-" to read from stdin.
+    #[arg(short, long, default_value = DEFAULT_PATH)]
+    pub input: Source,
+
+    /// Output format of answers.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// Draw the puzzle state instead of printing answers.
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub visualize: Option<render::Format>,
+
+    /// Record steps of the simulation to a file instead of printing answers,
+    /// replay it with `aoc replay`.
+    #[arg(long, value_name = "FILE")]
+    pub trace: Option<PathBuf>,
+
+    /// Print the graph of the puzzle in Graphviz DOT instead of answers.
+    #[arg(long, conflicts_with = "graphml")]
+    pub dot: bool,
+
+    /// Print the graph of the puzzle in GraphML instead of answers.
+    #[arg(long)]
+    pub graphml: bool,
+
+    /// Number of threads of solvers working in parallel, all cores by
+    /// default.
+    #[arg(long, value_name = "N", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub threads: Option<usize>,
+
+    /// Compare answers of optimized solvers with naive reference ones
+    /// instead of printing them, fails on any mismatch.
+    #[arg(long)]
+    pub cross_check: bool,
+
+    /// Solve the input one line at a time in bounded memory and print only
+    /// the answers, for line-oriented days.
+    #[arg(long)]
+    pub stream: bool,
+}
+
+impl Args {
+    /// Arguments of the running binary, exits with usage on invalid ones.
+    pub fn from_env() -> Self {
+        Args::parse()
+    }
+
+    /// Format of the requested graph export, if any.
+    pub fn graph(&self) -> Option<graph::Format> {
+        if self.dot {
+            Some(graph::Format::Dot)
+        } else if self.graphml {
+            Some(graph::Format::Graphml)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_source() {
+        assert_eq!("-".parse(), Ok(Source::Stdin));
+        assert_eq!(
+            "files/example.txt".parse(),
+            Ok(Source::File(PathBuf::from("files/example.txt")))
+        );
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Args::parse_from(["day1"]);
+        assert_eq!(args.input, Source::default());
+
+        let args = Args::parse_from(["day1", "--input", "-"]);
+        assert_eq!(args.input, Source::Stdin);
+        assert_eq!(args.format, Format::Text);
+
+        let args = Args::parse_from(["day1", "--format", "json"]);
+        assert_eq!(args.format, Format::Json);
+        assert_eq!(args.visualize, None);
+
+        let args = Args::parse_from(["day1", "--visualize", "svg"]);
+        assert_eq!(args.visualize, Some(render::Format::Svg));
+
+        let args = Args::parse_from(["day1", "--trace", "trace.json"]);
+        assert_eq!(args.trace, Some(PathBuf::from("trace.json")));
+        assert_eq!(args.graph(), None);
+
+        let args = Args::parse_from(["day1", "--dot"]);
+        assert_eq!(args.graph(), Some(graph::Format::Dot));
+
+        let args = Args::parse_from(["day1", "--graphml"]);
+        assert_eq!(args.graph(), Some(graph::Format::Graphml));
+
+        assert!(Args::try_parse_from(["day1", "--dot", "--graphml"]).is_err());
+        assert!(!args.cross_check);
+
+        let args = Args::parse_from(["day1", "--cross-check"]);
+        assert!(args.cross_check);
+        assert_eq!(args.threads, None);
+
+        let args = Args::parse_from(["day1", "--threads", "4"]);
+        assert_eq!(args.threads, Some(4));
+        assert!(Args::try_parse_from(["day1", "--threads", "0"]).is_err());
+        assert!(!args.stream);
+
+        let args = Args::parse_from(["day1", "--stream", "-i", "-"]);
+        assert!(args.stream);
+    }
+}
@@ -0,0 +1,17 @@
+//! Code shared between solutions of all days.
+
+pub mod check;
+pub mod error;
+pub mod generate;
+pub mod graph;
+pub mod input;
+pub mod math;
+pub mod memory;
+pub mod parallel;
+pub mod render;
+pub mod report;
+pub mod solution;
+pub mod stream;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod trace;
@@ -0,0 +1,212 @@
+//! Number theory helpers: gcd/lcm which report overflow instead of wrapping,
+//! extended Euclid, Chinese remainder theorem and modular exponentiation.
+
+use std::ops::{Div, Rem};
+
+/// Unsigned integers supported by [`gcd`] and [`lcm`].
+pub trait Unsigned: Copy + Eq + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const ZERO: Self = 0;
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+unsigned!(u64, u128);
+
+/// Greatest common divisor, `gcd(0, 0)` is 0.
+pub fn gcd<T: Unsigned>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, `None` if it doesn't fit into `T`.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    // Dividing first keeps intermediate value not larger than the result.
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Least common multiple of all numbers, `None` on overflow or when there are
+/// no numbers.
+pub fn lcm_all<T: Unsigned>(nums: impl IntoIterator<Item = T>) -> Option<T> {
+    let mut nums = nums.into_iter();
+    let first = nums.next()?;
+    nums.try_fold(first, lcm)
+}
+
+/// Extended Euclid: returns `(g, x, y)` such that `a * x + b * y = g` where
+/// `g` is non-negative gcd of `a` and `b`.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Solves system of congruences `x ≡ residue (mod modulus)`, moduli don't have
+/// to be coprime. Returns the smallest non-negative solution and the lcm of
+/// moduli, all solutions differ from it by multiples of the lcm.
+///
+/// `None` if the system has no solution, a modulus is not positive or the lcm
+/// overflows.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for &(residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+        let residue = residue.rem_euclid(modulus);
+
+        // x + m * k ≡ residue (mod modulus) => m * k ≡ residue - x (mod modulus)
+        let (g, p, _) = ext_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = mul_mod((diff / g).rem_euclid(step), p.rem_euclid(step), step);
+        let lcm = m.checked_mul(step)?;
+
+        x = (x + m.checked_mul(k)?).rem_euclid(lcm);
+        m = lcm;
+    }
+
+    Some((x, m))
+}
+
+/// `a * b mod m` for non-negative `a`, `b` less than `m`. Products which
+/// don't fit u128 are added up bit by bit, doubling `a`, which stays below
+/// `2 * m` and can't overflow.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    let m = m as u128;
+    let (mut a, mut b) = (a as u128, b as u128);
+    if let Some(product) = a.checked_mul(b) {
+        return (product % m) as i128;
+    }
+
+    let mut res = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            res = (res + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    res as i128
+}
+
+/// `base` raised to `exp` modulo `modulus`, `modulus` must not be 0.
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    let m = modulus as u128;
+    let mut base = base as u128 % m;
+    let mut exp = exp;
+    let mut res = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = res * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    res as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12_u64, 18), 6);
+        assert_eq!(gcd(0_u64, 5), 5);
+        assert_eq!(lcm(4_u64, 6), Some(12));
+        assert_eq!(lcm(0_u64, 6), Some(0));
+
+        // Product overflows but lcm itself fits.
+        assert_eq!(lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(lcm(u64::MAX as u128, 2), Some(u64::MAX as u128 * 2));
+
+        assert_eq!(lcm_all([2_u64, 3, 4]), Some(12));
+        assert_eq!(lcm_all(Vec::<u64>::new()), None);
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, -7)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i128);
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Not coprime moduli.
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(0, 0)]), None);
+
+        // Products of residues and moduli above 2^64 overflow, the lcm fits.
+        assert_eq!(
+            crt(&[(1, 3), ((1 << 69) + 12345, (1 << 70) + 1)]),
+            Some((1770887431076116967482, 3541774862152233910275))
+        );
+        // Lcm above i128::MAX.
+        assert_eq!(crt(&[(1, 1 << 64), (0, (1 << 63) + 1)]), None);
+    }
+
+    #[test]
+    fn test_mul_mod() {
+        assert_eq!(mul_mod(6, 7, 10), 2);
+        let m = i128::MAX;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod(m - 1, 2, m), m - 2);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    }
+}
@@ -0,0 +1,186 @@
+//! Counting of heap allocations to find solvers which allocate the most.
+//!
+//! A binary installs [`Counting`] as its global allocator and calls
+//! [`enable`], afterwards [`measure`] reports the allocations of a piece of
+//! code. Until then the allocator only forwards to the system one.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering::Relaxed},
+};
+
+use serde::Serialize;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+/// Bytes in use, can go below zero when memory allocated before counting was
+/// enabled is freed.
+static CURRENT: AtomicI64 = AtomicI64::new(0);
+static PEAK: AtomicI64 = AtomicI64::new(0);
+
+/// System allocator which counts allocations and bytes in use once enabled.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        freed(layout.size());
+    }
+
+    /// Counted as freeing the old block and allocating the new one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+fn allocated(size: usize) {
+    if ENABLED.load(Relaxed) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        ALLOCATED.fetch_add(size as u64, Relaxed);
+        let current = CURRENT.fetch_add(size as i64, Relaxed) + size as i64;
+        PEAK.fetch_max(current, Relaxed);
+    }
+}
+
+fn freed(size: usize) {
+    if ENABLED.load(Relaxed) {
+        CURRENT.fetch_sub(size as i64, Relaxed);
+    }
+}
+
+/// Starts counting, which only works with [`Counting`] installed as the
+/// global allocator.
+pub fn enable() {
+    ENABLED.store(true, Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Relaxed)
+}
+
+/// Heap usage of a measured piece of code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Usage {
+    pub allocations: u64,
+    /// Sum of the sizes of all allocations.
+    pub allocated_bytes: u64,
+    /// Most bytes in use at once above those in use at the start.
+    pub peak_bytes: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "allocs {:>9} peak {:>10}",
+            self.allocations,
+            bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Runs `f` and measures its heap usage if counting is enabled. Allocations
+/// of other threads running at the same time are counted as well.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let allocated = ALLOCATED.load(Relaxed);
+    let start = CURRENT.load(Relaxed);
+    PEAK.store(start, Relaxed);
+
+    let res = f();
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        allocated_bytes: ALLOCATED.load(Relaxed) - allocated,
+        peak_bytes: (PEAK.load(Relaxed) - start).max(0) as u64,
+    };
+    (res, Some(usage))
+}
+
+/// Number of bytes in the largest binary unit under 1024 of it.
+fn bytes(n: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if n < 1024 {
+        return format!("{} B", n);
+    }
+    let mut value = n as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn test_measure() {
+        enable();
+
+        let (len, usage) = measure(|| {
+            let v = vec![1_u8; 100_000];
+            let w: Vec<u8> = v.iter().map(|b| b + 1).collect();
+            w.len()
+        });
+        let usage = usage.unwrap();
+
+        assert_eq!(len, 100_000);
+        // Tests running at the same time may allocate as well.
+        assert!(usage.allocations >= 2);
+        assert!(usage.allocated_bytes >= 200_000);
+        assert!(usage.peak_bytes >= 200_000);
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1536), "1.5 KiB");
+        assert_eq!(bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_display() {
+        let usage = Usage {
+            allocations: 12,
+            allocated_bytes: 4096,
+            peak_bytes: 2048,
+        };
+
+        assert_eq!(usage.to_string(), "allocs        12 peak    2.0 KiB");
+    }
+}
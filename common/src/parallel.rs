@@ -0,0 +1,65 @@
+//! Parallel execution of independent work units on a thread pool shared by
+//! all days, so the number of threads is set in one place.
+
+use std::ops::Range;
+
+use rayon::prelude::*;
+
+use crate::error::{Error, Result};
+
+/// Sets the number of threads of the shared pool, which works only before
+/// the pool is first used.
+pub fn set_threads(threads: usize) -> Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|err| Error::new(format!("thread pool: {}", err)))
+}
+
+/// Number of threads of the shared pool, all cores unless set.
+pub fn threads() -> usize {
+    rayon::current_num_threads()
+}
+
+/// Results of `f` for all `items`, computed in parallel and kept in the order
+/// of `items`.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    items.par_iter().map(f).collect()
+}
+
+/// Results of `f` for all numbers of `range`, computed in parallel and kept
+/// in order.
+pub fn map_range<R, F>(range: Range<usize>, f: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(usize) -> R + Sync + Send,
+{
+    range.into_par_iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let items: Vec<u64> = (0..1000).collect();
+
+        assert_eq!(
+            map(&items, |n| n * n),
+            items.iter().map(|n| n * n).collect::<Vec<_>>()
+        );
+        assert_eq!(map(&[] as &[u64], |n| n * n), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_map_range() {
+        assert_eq!(map_range(3..8, |i| i * 2), vec![6, 8, 10, 12, 14]);
+        assert!(threads() >= 1);
+    }
+}
@@ -0,0 +1,420 @@
+//! Rendering of grid puzzles as plain text, ANSI coloured text or SVG, with
+//! paths, visited cells and highlights drawn over the map.
+
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Output format of a rendered canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Plain characters, styles are not shown.
+    Ascii,
+    /// Characters coloured with ANSI escape codes for terminals.
+    Ansi,
+    /// Standalone SVG image.
+    Svg,
+}
+
+/// How a cell is emphasized over the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Style {
+    /// Cell on the path of a solution.
+    Path,
+    /// Cell reached by a search or a beam.
+    Visited,
+    /// Cell of special interest, e.g. start or end.
+    Highlight,
+}
+
+impl Style {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Style::Path => "\x1b[1;33m",
+            Style::Visited => "\x1b[36m",
+            Style::Highlight => "\x1b[1;31m",
+        }
+    }
+
+    /// Colour of the style in images.
+    pub fn rgb(&self) -> [u8; 3] {
+        match self {
+            Style::Path => [0xf5, 0xc5, 0x42],
+            Style::Visited => [0x9f, 0xd8, 0xef],
+            Style::Highlight => [0xe5, 0x53, 0x3d],
+        }
+    }
+
+    fn svg(&self) -> String {
+        let [r, g, b] = self.rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+
+    /// Single character code of an optional style in serialized canvases.
+    fn code(style: Option<Style>) -> char {
+        match style {
+            None => ' ',
+            Some(Style::Path) => 'p',
+            Some(Style::Visited) => 'v',
+            Some(Style::Highlight) => 'h',
+        }
+    }
+
+    fn from_code(code: char) -> Option<Option<Style>> {
+        match code {
+            ' ' => Some(None),
+            'p' => Some(Some(Style::Path)),
+            'v' => Some(Some(Style::Visited)),
+            'h' => Some(Some(Style::Highlight)),
+            _ => None,
+        }
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Size of a cell in SVG output in pixels.
+const SVG_CELL: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Cell {
+    pub(crate) ch: char,
+    pub(crate) style: Option<Style>,
+}
+
+/// Rectangle of characters with optional styles, positions out of the canvas
+/// are ignored so overlays don't have to be clipped by callers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "CanvasRepr", try_from = "CanvasRepr")]
+pub struct Canvas {
+    pub(crate) cells: Vec<Cell>,
+    rows: usize,
+    cols: usize,
+}
+
+impl Canvas {
+    /// Canvas filled with `fill`.
+    pub fn new(rows: usize, cols: usize, fill: char) -> Self {
+        Canvas {
+            cells: vec![
+                Cell {
+                    ch: fill,
+                    style: None
+                };
+                rows * cols
+            ],
+            rows,
+            cols,
+        }
+    }
+
+    /// Canvas with a row per line of `text`, shorter lines are padded with
+    /// spaces.
+    pub fn from_text(text: &str) -> Self {
+        let rows = text.lines().count();
+        let cols = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut canvas = Canvas::new(rows, cols, ' ');
+
+        for (row, line) in text.lines().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                canvas.cells[row * cols + col].ch = ch;
+            }
+        }
+
+        canvas
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub(crate) fn cell_mut(&mut self, (row, col): (usize, usize)) -> Option<&mut Cell> {
+        if row < self.rows && col < self.cols {
+            Some(&mut self.cells[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    /// Character at a position, `None` out of the canvas.
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<char> {
+        (row < self.rows && col < self.cols).then(|| self.cells[row * self.cols + col].ch)
+    }
+
+    /// Style at a position, `None` if there is none or it's out of the canvas.
+    pub fn style(&self, (row, col): (usize, usize)) -> Option<Style> {
+        (row < self.rows && col < self.cols)
+            .then(|| self.cells[row * self.cols + col].style)
+            .flatten()
+    }
+
+    /// Replaces a character keeping its style.
+    pub fn set(&mut self, pos: impl Into<(usize, usize)>, ch: char) {
+        if let Some(cell) = self.cell_mut(pos.into()) {
+            cell.ch = ch;
+        }
+    }
+
+    /// Replaces a character and its style.
+    pub fn draw(&mut self, pos: impl Into<(usize, usize)>, ch: char, style: Style) {
+        if let Some(cell) = self.cell_mut(pos.into()) {
+            *cell = Cell {
+                ch,
+                style: Some(style),
+            };
+        }
+    }
+
+    /// Styles a cell keeping its character.
+    pub fn mark(&mut self, pos: impl Into<(usize, usize)>, style: Style) {
+        if let Some(cell) = self.cell_mut(pos.into()) {
+            cell.style = Some(style);
+        }
+    }
+
+    /// Styles all given cells, e.g. a path or a visited set.
+    pub fn mark_all<P: Into<(usize, usize)>>(
+        &mut self,
+        positions: impl IntoIterator<Item = P>,
+        style: Style,
+    ) {
+        for pos in positions {
+            self.mark(pos, style);
+        }
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Ascii => self.ascii(),
+            Format::Ansi => self.ansi(),
+            Format::Svg => self.svg(),
+        }
+    }
+
+    fn iter_rows(&self) -> impl Iterator<Item = &[Cell]> {
+        // `max(1)` because chunks of size 0 are not allowed, there are no
+        // cells then anyway.
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    fn ascii(&self) -> String {
+        let mut res = String::with_capacity(self.rows * (self.cols + 1));
+        for row in self.iter_rows() {
+            res.extend(row.iter().map(|c| c.ch));
+            res.push('\n');
+        }
+        res
+    }
+
+    fn ansi(&self) -> String {
+        let mut res = String::new();
+
+        for row in self.iter_rows() {
+            let mut current = None;
+            for cell in row {
+                if cell.style != current {
+                    if current.is_some() {
+                        res.push_str(ANSI_RESET);
+                    }
+                    if let Some(style) = cell.style {
+                        res.push_str(style.ansi());
+                    }
+                    current = cell.style;
+                }
+                res.push(cell.ch);
+            }
+            if current.is_some() {
+                res.push_str(ANSI_RESET);
+            }
+            res.push('\n');
+        }
+
+        res
+    }
+
+    fn svg(&self) -> String {
+        let (width, height) = (self.cols * SVG_CELL, self.rows * SVG_CELL);
+        let mut res = String::new();
+
+        // Writing to a String never fails.
+        let _ = writeln!(
+            res,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        let _ = writeln!(
+            res,
+            r#"<rect width="{width}" height="{height}" fill="white"/>"#
+        );
+        let _ = writeln!(
+            res,
+            r#"<g font-family="monospace" font-size="{}" text-anchor="middle">"#,
+            SVG_CELL - 2
+        );
+
+        for (row, cells) in self.iter_rows().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                let (x, y) = (col * SVG_CELL, row * SVG_CELL);
+
+                if let Some(style) = cell.style {
+                    let _ = writeln!(
+                        res,
+                        r#"<rect x="{x}" y="{y}" width="{SVG_CELL}" height="{SVG_CELL}" fill="{}"/>"#,
+                        style.svg()
+                    );
+                }
+                if !cell.ch.is_whitespace() {
+                    let _ = writeln!(
+                        res,
+                        r#"<text x="{}" y="{}">{}</text>"#,
+                        x + SVG_CELL / 2,
+                        y + SVG_CELL - 2,
+                        escape(cell.ch)
+                    );
+                }
+            }
+        }
+
+        res.push_str("</g>\n</svg>\n");
+        res
+    }
+}
+
+/// Serialized canvas: characters and codes of styles, row by row.
+#[derive(Serialize, Deserialize)]
+struct CanvasRepr {
+    rows: usize,
+    cols: usize,
+    text: String,
+    styles: String,
+}
+
+impl From<Canvas> for CanvasRepr {
+    fn from(canvas: Canvas) -> Self {
+        CanvasRepr {
+            rows: canvas.rows,
+            cols: canvas.cols,
+            text: canvas.cells.iter().map(|c| c.ch).collect(),
+            styles: canvas.cells.iter().map(|c| Style::code(c.style)).collect(),
+        }
+    }
+}
+
+impl TryFrom<CanvasRepr> for Canvas {
+    type Error = String;
+
+    fn try_from(repr: CanvasRepr) -> Result<Self, Self::Error> {
+        let styles = repr
+            .styles
+            .chars()
+            .map(|code| Style::from_code(code).ok_or(format!("invalid style \"{}\"", code)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let cells: Vec<Cell> = repr
+            .text
+            .chars()
+            .zip(styles.iter())
+            .map(|(ch, &style)| Cell { ch, style })
+            .collect();
+
+        let size = repr.rows * repr.cols;
+        if cells.len() != size || styles.len() != size || repr.text.chars().count() != size {
+            return Err(format!("expected {} cells", size));
+        }
+
+        Ok(Canvas {
+            cells,
+            rows: repr.rows,
+            cols: repr.cols,
+        })
+    }
+}
+
+/// Character escaped for XML text.
+fn escape(ch: char) -> String {
+    match ch {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        ch => ch.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas() -> Canvas {
+        let mut canvas = Canvas::from_text("#..\n.<#\n");
+        canvas.draw((0, 1), '>', Style::Path);
+        canvas.mark((0, 2), Style::Path);
+        canvas.mark((1, 0), Style::Highlight);
+        // Out of the canvas.
+        canvas.mark((2, 0), Style::Visited);
+        canvas
+    }
+
+    #[test]
+    fn test_canvas() {
+        let c = canvas();
+
+        assert_eq!((c.rows(), c.cols()), (2, 3));
+        assert_eq!(c.get((0, 1)), Some('>'));
+        assert_eq!(c.style((0, 1)), Some(Style::Path));
+        assert_eq!(c.style((0, 0)), None);
+        assert_eq!(c.get((2, 0)), None);
+
+        assert_eq!(
+            Canvas::from_text("ab\nc\n").render(Format::Ascii),
+            "ab\nc \n"
+        );
+        assert_eq!(Canvas::from_text("").render(Format::Ascii), "");
+    }
+
+    #[test]
+    fn test_ascii() {
+        assert_eq!(canvas().render(Format::Ascii), "#>.\n.<#\n");
+    }
+
+    #[test]
+    fn test_ansi() {
+        assert_eq!(
+            canvas().render(Format::Ansi),
+            "#\x1b[1;33m>.\x1b[0m\n\x1b[1;31m.\x1b[0m<#\n"
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = canvas().render(Format::Svg);
+
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="36" height="24""#)
+        );
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches(r##"fill="#f5c542""##).count(), 2);
+        assert_eq!(svg.matches(r##"fill="#e5533d""##).count(), 1);
+        assert!(svg.contains(">&gt;</text>"));
+        assert!(svg.contains(">&lt;</text>"));
+        // Dots are drawn too, spaces are not.
+        assert_eq!(svg.matches("<text").count(), 6);
+    }
+
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&canvas()).unwrap();
+        assert_eq!(
+            json,
+            r##"{"rows":2,"cols":3,"text":"#>..<#","styles":" pph  "}"##
+        );
+        assert_eq!(serde_json::from_str::<Canvas>(&json).unwrap(), canvas());
+
+        let err =
+            serde_json::from_str::<Canvas>(r##"{"rows":2,"cols":3,"text":"#","styles":" "}"##);
+        assert!(err.unwrap_err().to_string().contains("expected 6 cells"));
+    }
+}
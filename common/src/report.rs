@@ -0,0 +1,225 @@
+//! Results of solved puzzle parts, printed as text or as JSON records.
+
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use serde::{Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::{Error, Result},
+    memory::{self, Usage},
+    solution::Solution,
+};
+
+/// Output format of results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One aligned line per part.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+/// Answer of a single part with the time spent on it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    #[serde(rename = "parse_ns", serialize_with = "nanos")]
+    pub parse_time: Duration,
+    #[serde(rename = "solve_ns", serialize_with = "nanos")]
+    pub solve_time: Duration,
+    /// SHA-256 of the puzzle input, tells apart results for different inputs.
+    pub input_hash: String,
+    /// Heap usage of parsing and solving, when allocations are counted.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Usage>,
+}
+
+impl Record {
+    /// Parses `input` and solves the given part of the puzzle, timing both
+    /// steps and measuring their heap usage if enabled.
+    pub fn solve<S: Solution>(part: u8, input: &str) -> Result<Record> {
+        match part {
+            1 => Record::measure(S::DAY, 1, input, S::parse, S::part1),
+            2 if S::PARTS >= 2 => Record::measure(S::DAY, 2, input, S::parse, S::part2),
+            _ => Err(Error::new(format!("day {} has no part {}", S::DAY, part))),
+        }
+    }
+
+    fn measure<I, T: Display>(
+        day: u8,
+        part: u8,
+        input: &str,
+        parse: fn(&str) -> Result<I>,
+        solve: fn(&I) -> Result<T>,
+    ) -> Result<Record> {
+        let (res, memory) = memory::measure(|| -> Result<_> {
+            let start = Instant::now();
+            let parsed = parse(input)?;
+            let parse_time = start.elapsed();
+
+            let start = Instant::now();
+            let answer = solve(&parsed)?.to_string();
+            let solve_time = start.elapsed();
+
+            Ok((answer, parse_time, solve_time))
+        });
+        let (answer, parse_time, solve_time) = res?;
+
+        Ok(Record {
+            day,
+            part,
+            answer,
+            parse_time,
+            solve_time,
+            input_hash: hash(input),
+            memory,
+        })
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("record is always serializable")
+    }
+
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => println!("{}", self),
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {:>2} part {}: {:<20} parse {:>10.3?} solve {:>10.3?}",
+            self.day, self.part, self.answer, self.parse_time, self.solve_time
+        )?;
+        if let Some(memory) = &self.memory {
+            write!(f, " {}", memory)?;
+        }
+        Ok(())
+    }
+}
+
+fn nanos<S: Serializer>(d: &Duration, s: S) -> std::result::Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_nanos() as u64)
+}
+
+/// Hex encoded SHA-256 of puzzle input.
+pub fn hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(
+            hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let record = Record {
+            day: 6,
+            part: 2,
+            answer: "71503".to_string(),
+            parse_time: Duration::from_micros(3),
+            solve_time: Duration::from_nanos(120),
+            input_hash: hash("abc"),
+            memory: None,
+        };
+
+        assert_eq!(
+            record.to_json(),
+            "{\"day\":6,\"part\":2,\"answer\":\"71503\",\"parse_ns\":3000,\"solve_ns\":120,\
+             \"input_hash\":\"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\"}"
+        );
+    }
+
+    #[test]
+    fn test_to_json_memory() {
+        let record = Record {
+            day: 6,
+            part: 2,
+            answer: "71503".to_string(),
+            parse_time: Duration::from_micros(3),
+            solve_time: Duration::from_nanos(120),
+            input_hash: hash("abc"),
+            memory: Some(Usage {
+                allocations: 4,
+                allocated_bytes: 256,
+                peak_bytes: 192,
+            }),
+        };
+
+        assert!(record
+            .to_json()
+            .ends_with("\"allocations\":4,\"allocated_bytes\":256,\"peak_bytes\":192}"));
+        assert!(record
+            .to_string()
+            .ends_with("allocs         4 peak      192 B"));
+    }
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+        const PARTS: u8 = 1;
+
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> Result<Vec<u32>> {
+            input.split(' ').map(|n| Ok(n.parse()?)).collect()
+        }
+
+        fn part1(input: &Vec<u32>) -> Result<u32> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(_: &Vec<u32>) -> Result<u32> {
+            unreachable!()
+        }
+
+        fn generate(size: usize, rng: &mut Rng) -> String {
+            let numbers: Vec<String> = (0..size.max(1))
+                .map(|_| rng.usize(0..100).to_string())
+                .collect();
+            numbers.join(" ")
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let record = Record::solve::<Sum>(1, "1 2 3").unwrap();
+
+        assert_eq!(record.day, 1);
+        assert_eq!(record.answer, "6");
+        assert_eq!(record.input_hash, hash("1 2 3"));
+
+        assert_eq!(
+            Record::solve::<Sum>(2, "1 2 3").unwrap_err().message(),
+            "day 1 has no part 2"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Interface implemented by solutions of all days, so that binaries, the
+//! runner, tests and benchmarks drive any day the same way.
+
+use std::{fmt::Display, io::BufRead};
+
+use crate::{
+    check::Check,
+    error::{Error, Result},
+    generate::Rng,
+    graph::Graph,
+    input::Args,
+    parallel,
+    render::Canvas,
+    report::Record,
+    trace::Trace,
+};
+
+pub trait Solution {
+    /// Day of the puzzle, 1-25.
+    const DAY: u8;
+    /// Number of puzzle parts, the last day has a single one.
+    const PARTS: u8 = 2;
+
+    /// Puzzle input parsed once and shared by both parts.
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// Random valid puzzle input which grows with `size`, what the size
+    /// counts is up to each day: lines, rows of a map, bricks...
+    fn generate(size: usize, rng: &mut Rng) -> String;
+
+    /// Parses `input` and solves the first part.
+    fn solve1(input: &str) -> Result<Self::Answer1> {
+        Self::part1(&Self::parse(input)?)
+    }
+
+    /// Parses `input` and solves the second part.
+    fn solve2(input: &str) -> Result<Self::Answer2> {
+        Self::part2(&Self::parse(input)?)
+    }
+
+    /// Picture of the puzzle state with the solution drawn over it, only days
+    /// working on a map have one.
+    fn visualize(_input: &Self::Input) -> Result<Canvas> {
+        Err(Error::new(format!(
+            "day {} has no visualization",
+            Self::DAY
+        )))
+    }
+
+    /// Records frames of the simulation the solution runs, only simulation
+    /// days have one.
+    fn trace(_input: &Self::Input, _trace: &mut Trace) -> Result<()> {
+        Err(Error::new(format!("day {} has no trace", Self::DAY)))
+    }
+
+    /// Graph the puzzle input describes, only days working on a graph have
+    /// one.
+    fn graph(_input: &Self::Input) -> Result<Graph> {
+        Err(Error::new(format!("day {} has no graph", Self::DAY)))
+    }
+
+    /// Answers of naive reference solvers next to the ones of the optimized
+    /// solvers, only days with a shortcut worth verifying have them.
+    fn cross_check(_input: &Self::Input) -> Result<Vec<Check>> {
+        Err(Error::new(format!("day {} has no cross-check", Self::DAY)))
+    }
+
+    /// Answers of both parts of input read one line at a time in bounded
+    /// memory, only days which need a single line at a time can stream.
+    fn stream(_reader: &mut dyn BufRead) -> Result<(Self::Answer1, Self::Answer2)> {
+        Err(Error::new(format!(
+            "day {} can't stream its input",
+            Self::DAY
+        )))
+    }
+}
+
+/// Answer of a part which doesn't exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoAnswer {}
+
+impl Display for NoAnswer {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {}
+    }
+}
+
+/// Solves all parts of the input selected with command line arguments and
+/// prints the results, or prints the visualization, graph or cross-checks or
+/// records the trace if one is requested. Whole `main` of a day binary.
+pub fn run<S: Solution>() -> Result<()> {
+    run_with::<S>(Args::from_env())
+}
+
+/// Like [`run`] with arguments already parsed, for binaries of days with
+/// options of their own next to the common ones.
+pub fn run_with<S: Solution>(args: Args) -> Result<()> {
+    if let Some(threads) = args.threads {
+        parallel::set_threads(threads)?;
+    }
+
+    if args.stream {
+        let (answer1, answer2) = S::stream(&mut args.input.reader()?)?;
+        println!("day {:>2} part 1: {}", S::DAY, answer1);
+        println!("day {:>2} part 2: {}", S::DAY, answer2);
+        return Ok(());
+    }
+
+    let input = args.input.read()?;
+
+    if let Some(format) = args.visualize {
+        print!("{}", S::visualize(&S::parse(&input)?)?.render(format));
+        return Ok(());
+    }
+
+    if let Some(format) = args.graph() {
+        print!("{}", S::graph(&S::parse(&input)?)?.render(format));
+        return Ok(());
+    }
+
+    if args.cross_check {
+        let checks = S::cross_check(&S::parse(&input)?)?;
+        for check in &checks {
+            println!("{}", check);
+        }
+        let failed = checks.iter().filter(|c| !c.passed()).count();
+        if failed > 0 {
+            return Err(Error::new(format!(
+                "{} of {} cross-checks failed",
+                failed,
+                checks.len()
+            )));
+        }
+        return Ok(());
+    }
+
+    if let Some(path) = args.trace {
+        let mut trace = Trace::new(S::DAY);
+        S::trace(&S::parse(&input)?, &mut trace)?;
+        trace.save(&path)?;
+        println!("{} frames recorded to {}", trace.len(), path.display());
+        return Ok(());
+    }
+
+    for part in 1..=S::PARTS {
+        Record::solve::<S>(part, &input)?.print(args.format);
+    }
+
+    Ok(())
+}
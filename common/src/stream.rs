@@ -0,0 +1,150 @@
+//! Streaming of line-oriented puzzle inputs. Lines are read into a single
+//! reused buffer, parsed one at a time and folded into the answers, so inputs
+//! of any size are solved in bounded memory.
+
+use std::{io::BufRead, marker::PhantomData};
+
+use crate::{error::Result, solution::Solution};
+
+/// Solution which needs only one line of input at a time.
+pub trait Streaming: Solution {
+    /// Parsed line of input.
+    type Line;
+    /// Answers of both parts accumulated from the lines seen so far.
+    type State: Default;
+
+    fn parse_line(line: &str) -> Result<Self::Line>;
+
+    /// Adds a line to the answers, fails if a part can't be solved for it.
+    fn push(state: &mut Self::State, line: &Self::Line) -> Result<()>;
+
+    fn answers(state: &Self::State) -> (Self::Answer1, Self::Answer2);
+}
+
+/// Iterator over parsed lines of a reader, errors are located at their line.
+pub struct Lines<S, R> {
+    reader: R,
+    buf: String,
+    index: usize,
+    solution: PhantomData<S>,
+}
+
+impl<S: Streaming, R: BufRead> Iterator for Lines<S, R> {
+    type Item = Result<S::Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buf.clear();
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                let line = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                let res = S::parse_line(line).map_err(|err| err.at_line(self.index, line));
+                self.index += 1;
+                Some(res)
+            }
+            Err(err) => Some(Err(err.into())),
+        }
+    }
+}
+
+/// Parsed lines of the input read from `reader`.
+pub fn lines<S: Streaming, R: BufRead>(reader: R) -> Lines<S, R> {
+    Lines {
+        reader,
+        buf: String::new(),
+        index: 0,
+        solution: PhantomData,
+    }
+}
+
+/// Answers of both parts of the input read from `reader`, stops at the first
+/// invalid line.
+pub fn solve<S: Streaming>(reader: impl BufRead) -> Result<(S::Answer1, S::Answer2)> {
+    let mut state = S::State::default();
+    for line in lines::<S, _>(reader) {
+        S::push(&mut state, &line?)?;
+    }
+    Ok(S::answers(&state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, generate::Rng};
+
+    /// Sums of numbers and of their squares, one number per line.
+    struct Squares;
+
+    impl Solution for Squares {
+        const DAY: u8 = 1;
+
+        type Input = Vec<u64>;
+        type Answer1 = u64;
+        type Answer2 = u64;
+
+        fn parse(input: &str) -> Result<Vec<u64>> {
+            lines::<Self, _>(input.as_bytes()).collect()
+        }
+
+        fn part1(input: &Vec<u64>) -> Result<u64> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Vec<u64>) -> Result<u64> {
+            Ok(input.iter().map(|n| n * n).sum())
+        }
+
+        fn generate(size: usize, rng: &mut Rng) -> String {
+            (0..size).map(|_| format!("{}\n", rng.below(100))).collect()
+        }
+    }
+
+    impl Streaming for Squares {
+        type Line = u64;
+        type State = (u64, u64);
+
+        fn parse_line(line: &str) -> Result<u64> {
+            line.parse().map_err(|_| Error::invalid("number", line))
+        }
+
+        fn push(state: &mut (u64, u64), n: &u64) -> Result<()> {
+            state.0 += n;
+            state.1 += n * n;
+            Ok(())
+        }
+
+        fn answers(state: &(u64, u64)) -> (u64, u64) {
+            *state
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        let parsed: Result<Vec<u64>> = lines::<Squares, _>("1\n2\r\n3".as_bytes()).collect();
+        assert_eq!(parsed, Ok(vec![1, 2, 3]));
+
+        let err = lines::<Squares, _>("1\n2\nx3\n".as_bytes())
+            .nth(2)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.message(), "invalid number");
+        assert_eq!(err.location().unwrap().line, 3);
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = Squares::generate(100, &mut Rng::new(7));
+        let parsed = Squares::parse(&input).unwrap();
+
+        assert_eq!(
+            solve::<Squares>(input.as_bytes()),
+            Ok((
+                Squares::part1(&parsed).unwrap(),
+                Squares::part2(&parsed).unwrap()
+            ))
+        );
+        assert_eq!(solve::<Squares>("".as_bytes()), Ok((0, 0)));
+        assert!(solve::<Squares>("1\n-\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Proptest strategies for tests of day parsers and stress tests of solvers.
+//!
+//! Arbitrary strings rarely get past the first check of a parser, so inputs
+//! are also produced by randomly editing a valid example.
+
+use proptest::{prelude::*, sample::Index};
+
+use crate::{generate::Rng, solution::Solution};
+
+/// Single edit of a text.
+#[derive(Debug, Clone)]
+enum Edit {
+    Replace(Index, char),
+    Insert(Index, char),
+    Remove(Index),
+}
+
+/// `example` with a few characters replaced, inserted or removed. New
+/// characters are mostly taken from the example itself.
+pub fn mutated(example: &'static str) -> impl Strategy<Value = String> {
+    let chars: Vec<char> = example.chars().collect();
+    let ch = if chars.is_empty() {
+        any::<char>().boxed()
+    } else {
+        prop_oneof![1 => any::<char>(), 3 => prop::sample::select(chars.clone())].boxed()
+    };
+
+    let edit = prop_oneof![
+        (any::<Index>(), ch.clone()).prop_map(|(i, c)| Edit::Replace(i, c)),
+        (any::<Index>(), ch).prop_map(|(i, c)| Edit::Insert(i, c)),
+        any::<Index>().prop_map(Edit::Remove),
+    ];
+
+    prop::collection::vec(edit, 1..8).prop_map(move |edits| {
+        let mut text = chars.clone();
+        for edit in edits {
+            match edit {
+                Edit::Replace(i, c) if !text.is_empty() => {
+                    let i = i.index(text.len());
+                    text[i] = c;
+                }
+                Edit::Insert(i, c) => {
+                    let i = i.index(text.len() + 1);
+                    text.insert(i, c);
+                }
+                Edit::Remove(i) if !text.is_empty() => {
+                    text.remove(i.index(text.len()));
+                }
+                _ => {}
+            }
+        }
+        text.into_iter().collect()
+    })
+}
+
+/// Arbitrary text, mostly printable ASCII split into lines.
+pub fn text() -> impl Strategy<Value = String> {
+    prop_oneof![any::<String>(), "[ -~\n]{0,200}"]
+}
+
+/// Input which a parser must handle without panicking.
+pub fn input(example: &'static str) -> impl Strategy<Value = String> {
+    prop_oneof![1 => text(), 3 => mutated(example)]
+}
+
+/// Rectangular grid of `cells` with up to `size` rows and columns, as text
+/// with a newline after each row.
+pub fn grid(cells: &'static str, size: usize) -> impl Strategy<Value = String> {
+    let cells: Vec<char> = cells.chars().collect();
+    (1..=size, 1..=size)
+        .prop_flat_map(move |(rows, cols)| {
+            let row = prop::collection::vec(prop::sample::select(cells.clone()), cols);
+            prop::collection::vec(row, rows)
+        })
+        .prop_map(|rows| {
+            rows.into_iter()
+                .map(|row| row.into_iter().collect::<String>() + "\n")
+                .collect()
+        })
+}
+
+/// Lines joined with a newline after each.
+pub fn lines<T: ToString>(lines: &[T]) -> String {
+    lines.iter().map(|l| l.to_string() + "\n").collect()
+}
+
+/// Seeds of the inputs solved by [`stress`].
+pub const STRESS_SEEDS: u64 = 3;
+
+/// Solves all parts of inputs generated with `size` and a few seeds, panics
+/// with the seed if parsing or any part fails.
+pub fn stress<S: Solution>(size: usize) {
+    for seed in 0..STRESS_SEEDS {
+        let text = S::generate(size, &mut Rng::new(seed));
+        let input = S::parse(&text)
+            .unwrap_or_else(|err| panic!("day {} seed {}: parse: {}", S::DAY, seed, err));
+
+        if let Err(err) = S::part1(&input) {
+            panic!("day {} seed {}: part 1: {}", S::DAY, seed, err);
+        }
+        if S::PARTS > 1 {
+            if let Err(err) = S::part2(&input) {
+                panic!("day {} seed {}: part 2: {}", S::DAY, seed, err);
+            }
+        }
+    }
+}
+
+/// Cross-checks solvers on inputs generated with `size` and a few seeds,
+/// panics with the seed and the check on a mismatch.
+pub fn cross_check<S: Solution>(size: usize) {
+    for seed in 0..STRESS_SEEDS {
+        let text = S::generate(size, &mut Rng::new(seed));
+        let input = S::parse(&text)
+            .unwrap_or_else(|err| panic!("day {} seed {}: parse: {}", S::DAY, seed, err));
+        let checks = S::cross_check(&input)
+            .unwrap_or_else(|err| panic!("day {} seed {}: {}", S::DAY, seed, err));
+
+        for check in checks {
+            assert!(check.passed(), "day {} seed {}: {}", S::DAY, seed, check);
+        }
+    }
+}
+
+/// Streams inputs generated with `size` and a few seeds, panics with the
+/// seed if the answers differ from the ones of the parsed input.
+pub fn stream<S: Solution>(size: usize) {
+    for seed in 0..STRESS_SEEDS {
+        let text = S::generate(size, &mut Rng::new(seed));
+        let input = S::parse(&text)
+            .unwrap_or_else(|err| panic!("day {} seed {}: parse: {}", S::DAY, seed, err));
+        let (answer1, answer2) = S::stream(&mut text.as_bytes())
+            .unwrap_or_else(|err| panic!("day {} seed {}: stream: {}", S::DAY, seed, err));
+
+        let expected = (
+            S::part1(&input).unwrap().to_string(),
+            S::part2(&input).unwrap().to_string(),
+        );
+        assert_eq!(
+            (answer1.to_string(), answer2.to_string()),
+            expected,
+            "day {} seed {}",
+            S::DAY,
+            seed
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_mutated(s in mutated("abc\ndef\n")) {
+            // Up to 7 edits of 8 characters.
+            prop_assert!((1..=15).contains(&s.chars().count()));
+        }
+
+        #[test]
+        fn test_grid(s in grid("#.", 5)) {
+            let lines: Vec<&str> = s.lines().collect();
+            prop_assert!((1..=5).contains(&lines.len()));
+            prop_assert!(lines.iter().all(|l| l.len() == lines[0].len()));
+        }
+    }
+}
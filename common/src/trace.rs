@@ -0,0 +1,164 @@
+//! Recording of simulation steps as frames for replaying them later.
+//!
+//! Only the first frame is stored whole, following ones are stored as the
+//! cells which changed since the previous frame, so long simulations where
+//! each step changes a few cells stay small.
+
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    render::{Canvas, Cell, Style},
+};
+
+/// Changed cell: row, column, new character and style.
+type Change = (usize, usize, char, Option<Style>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Step {
+    /// Whole frame, used for the first one and when the size changes.
+    Full(Canvas),
+    Diff(Vec<Change>),
+}
+
+/// Frames of a simulation of a day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trace {
+    pub day: u8,
+    steps: Vec<Step>,
+    /// Last recorded frame which the next one is compared with.
+    #[serde(skip)]
+    last: Option<Canvas>,
+}
+
+impl Trace {
+    pub fn new(day: u8) -> Self {
+        Trace {
+            day,
+            steps: Vec::new(),
+            last: None,
+        }
+    }
+
+    /// Adds the next frame.
+    pub fn record(&mut self, frame: Canvas) {
+        let step = match &self.last {
+            Some(last) if last.rows() == frame.rows() && last.cols() == frame.cols() => {
+                let changes = last
+                    .cells
+                    .iter()
+                    .zip(frame.cells.iter())
+                    .enumerate()
+                    .filter(|(_, (a, b))| a != b)
+                    .map(|(i, (_, b))| (i / frame.cols(), i % frame.cols(), b.ch, b.style))
+                    .collect();
+                Step::Diff(changes)
+            }
+            _ => Step::Full(frame.clone()),
+        };
+
+        self.steps.push(step);
+        self.last = Some(frame);
+    }
+
+    /// Number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Recorded frames in order.
+    pub fn frames(&self) -> impl Iterator<Item = Canvas> + '_ {
+        self.steps
+            .iter()
+            .scan(Canvas::new(0, 0, ' '), |frame, step| {
+                match step {
+                    Step::Full(canvas) => *frame = canvas.clone(),
+                    Step::Diff(changes) => {
+                        for &(row, col, ch, style) in changes {
+                            if let Some(cell) = frame.cell_mut((row, col)) {
+                                *cell = Cell { ch, style };
+                            }
+                        }
+                    }
+                }
+                Some(frame.clone())
+            })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string(self).expect("trace is always serializable");
+        fs::write(path, text).map_err(|err| Error::new(format!("{}: {}", path.display(), err)))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| Error::new(format!("{}: {}", path.display(), err)))?;
+        serde_json::from_str(&text)
+            .map_err(|err| Error::new(format!("{}: {}", path.display(), err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<Canvas> {
+        let first = Canvas::from_text("...\n...\n");
+
+        let mut second = first.clone();
+        second.draw((0, 1), 'O', Style::Path);
+
+        let mut third = second.clone();
+        third.mark((1, 2), Style::Highlight);
+
+        vec![first, second, third, Canvas::from_text("..\n")]
+    }
+
+    fn trace() -> Trace {
+        let mut trace = Trace::new(14);
+        for frame in frames() {
+            trace.record(frame);
+        }
+        trace
+    }
+
+    #[test]
+    fn test_record() {
+        let trace = trace();
+
+        assert_eq!(trace.len(), 4);
+        assert!(matches!(trace.steps[0], Step::Full(_)));
+        assert_eq!(
+            trace.steps[1],
+            Step::Diff(vec![(0, 1, 'O', Some(Style::Path))])
+        );
+        assert_eq!(
+            trace.steps[2],
+            Step::Diff(vec![(1, 2, '.', Some(Style::Highlight))])
+        );
+        // Size changed.
+        assert!(matches!(trace.steps[3], Step::Full(_)));
+
+        assert_eq!(trace.frames().collect::<Vec<_>>(), frames());
+    }
+
+    #[test]
+    fn test_save_load() {
+        let path = std::env::temp_dir().join(format!("aoc-trace-{}.json", std::process::id()));
+
+        let trace = trace();
+        trace.save(&path).unwrap();
+        let loaded = Trace::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.day, 14);
+        assert_eq!(loaded.frames().collect::<Vec<_>>(), frames());
+    }
+}
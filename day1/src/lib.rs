@@ -0,0 +1,459 @@
+use std::{cmp::Reverse, io::BufRead, sync::LazyLock};
+
+use aho_corasick::AhoCorasick;
+use common::{
+    error::{Error, Result},
+    generate::Rng,
+    solution::Solution,
+    stream::{self, Streaming},
+};
+
+/// Calibration document, lines are scanned by each part on its own.
+pub struct Input(pub String);
+
+/// Dictionary of part 1, digits only.
+static DIGITS: LazyLock<Dictionary> = LazyLock::new(Dictionary::digits);
+/// Dictionary of part 2, digits and English words.
+static ENGLISH: LazyLock<Dictionary> = LazyLock::new(Dictionary::english);
+
+/// Day 1: Trebuchet?!
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(input.to_string()))
+    }
+
+    /// `size` lines of letters, digits and spelled out digits, each with at
+    /// least one digit.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut res = String::new();
+        for _ in 0..size {
+            let mut pieces: Vec<String> = (0..rng.usize(0..12))
+                .map(|_| match rng.below(3) {
+                    0 => rng.pick(&words::ENGLISH).to_string(),
+                    1 => rng.usize(1..10).to_string(),
+                    _ => ((b'a' + rng.below(26) as u8) as char).to_string(),
+                })
+                .collect();
+            let i = rng.usize(0..pieces.len() + 1);
+            pieces.insert(i, rng.usize(1..10).to_string());
+
+            res += &pieces.concat();
+            res.push('\n');
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        DIGITS.total(&input.0)
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        ENGLISH.total(&input.0)
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Result<(u64, u64)> {
+        stream::solve::<Self>(reader)
+    }
+}
+
+impl Day1 {
+    /// First and last tokens of each line with their byte offsets for both
+    /// parts, lines with different values in the parts are flagged.
+    pub fn explain(input: &Input) -> String {
+        let mut res = String::new();
+        let mut lines = 0;
+        let mut differ = 0;
+
+        for (i, line) in input.0.lines().enumerate() {
+            let ends1 = DIGITS.ends(line);
+            let ends2 = ENGLISH.ends(line);
+            let flag = if value(ends1) != value(ends2) {
+                differ += 1;
+                "  <- parts disagree"
+            } else {
+                ""
+            };
+
+            res += &format!("line {}: {}\n", i + 1, line);
+            res += &format!("  part 1: {}\n", describe(line, ends1));
+            res += &format!("  part 2: {}{}\n", describe(line, ends2), flag);
+            lines += 1;
+        }
+        res += &format!("{} of {} lines differ between the parts\n", differ, lines);
+        res
+    }
+}
+
+/// Calibration value of the first and the last token, if any.
+fn value(ends: Option<(Match, Match)>) -> Option<u32> {
+    ends.map(|(first, last)| 10 * first.value + last.value)
+}
+
+/// First and last token of `line` as "first "two" at 0, last "1" at 3 -> 21".
+fn describe(line: &str, ends: Option<(Match, Match)>) -> String {
+    match ends {
+        Some((first, last)) => format!(
+            "first \"{}\" at {}, last \"{}\" at {} -> {}",
+            first.token(line),
+            first.offset,
+            last.token(line),
+            last.offset,
+            10 * first.value + last.value
+        ),
+        None => "no digits".to_string(),
+    }
+}
+
+impl Streaming for Day1 {
+    /// Calibration values of the line for both parts.
+    type Line = (u32, u32);
+    type State = (u64, u64);
+
+    /// Lines without a digit for either part are invalid.
+    fn parse_line(line: &str) -> Result<(u32, u32)> {
+        Ok((DIGITS.calibration(line)?, ENGLISH.calibration(line)?))
+    }
+
+    fn push(sums: &mut (u64, u64), values: &(u32, u32)) -> Result<()> {
+        sums.0 += u64::from(values.0);
+        sums.1 += u64::from(values.1);
+        Ok(())
+    }
+
+    fn answers(sums: &(u64, u64)) -> (u64, u64) {
+        *sums
+    }
+}
+
+/// Digits 1 to 9 spelled out in a few languages and as roman numerals.
+pub mod words {
+    pub const ENGLISH: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    pub const GERMAN: [&str; 9] = [
+        "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+    ];
+    pub const FRENCH: [&str; 9] = [
+        "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+    ];
+    pub const ROMAN: [&str; 9] = ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"];
+}
+
+/// Tokens standing for digits in calibration lines, with their values. All
+/// tokens are searched at once with an Aho-Corasick automaton, which finds
+/// the first and the last token of a line in a single pass over it.
+#[derive(Debug, Clone)]
+pub struct Dictionary {
+    tokens: Vec<(String, u32)>,
+    automaton: AhoCorasick,
+}
+
+/// Token found in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Byte offset of the token in the line.
+    pub offset: usize,
+    /// Length of the token in bytes.
+    pub len: usize,
+    pub value: u32,
+}
+
+impl Match {
+    /// Text of the token in the `line` it was found in.
+    pub fn token<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.offset..self.offset + self.len]
+    }
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Dictionary::new(Vec::new())
+    }
+}
+
+impl Dictionary {
+    fn new(tokens: Vec<(String, u32)>) -> Self {
+        let automaton = AhoCorasick::new(tokens.iter().map(|(token, _)| token))
+            .expect("dictionary fits the automaton");
+        Dictionary { tokens, automaton }
+    }
+
+    /// Digits "0" to "9", the dictionary of part 1.
+    pub fn digits() -> Self {
+        Dictionary::new((0..10).map(|value| (value.to_string(), value)).collect())
+    }
+
+    /// Digits and English words "one" to "nine", the dictionary of part 2.
+    pub fn english() -> Self {
+        let mut dictionary = Dictionary::digits();
+        dictionary.add_words(&words::ENGLISH);
+        dictionary
+    }
+
+    /// Adds a token, values are single digits.
+    pub fn add(&mut self, token: &str, value: u32) -> Result<()> {
+        self.add_all(&[(token, value)])
+    }
+
+    /// Adds many tokens at once, rebuilding the automaton only once. Nothing
+    /// is added if any token is invalid.
+    pub fn add_all(&mut self, tokens: &[(&str, u32)]) -> Result<()> {
+        for &(token, value) in tokens {
+            if token.is_empty() {
+                return Err(Error::new("empty token"));
+            }
+            if value > 9 {
+                return Err(Error::new(format!(
+                    "value {} of token \"{}\" is not a digit",
+                    value, token
+                )));
+            }
+        }
+
+        let mut all = std::mem::take(&mut self.tokens);
+        all.extend(
+            tokens
+                .iter()
+                .map(|&(token, value)| (token.to_string(), value)),
+        );
+        *self = Dictionary::new(all);
+        Ok(())
+    }
+
+    /// Adds words for digits 1 to 9, such as one of [`words`].
+    pub fn add_words(&mut self, words: &[&str; 9]) {
+        let tokens: Vec<(&str, u32)> = words
+            .iter()
+            .zip(1..)
+            .map(|(&word, value)| (word, value))
+            .collect();
+        self.add_all(&tokens).expect("words are valid tokens");
+    }
+
+    /// First and last token of `line`, both the longest at their offset. The
+    /// last one may overlap the first one, as "one" in "twone".
+    pub fn ends(&self, line: &str) -> Option<(Match, Match)> {
+        let mut ends: Option<(Match, Match)> = None;
+
+        for found in self.automaton.find_overlapping_iter(line) {
+            let found = Match {
+                offset: found.start(),
+                len: found.len(),
+                value: self.tokens[found.pattern().as_usize()].1,
+            };
+            ends = Some(match ends {
+                None => (found, found),
+                Some((first, last)) => (
+                    if (found.offset, Reverse(found.len)) < (first.offset, Reverse(first.len)) {
+                        found
+                    } else {
+                        first
+                    },
+                    if (found.offset, found.len) > (last.offset, last.len) {
+                        found
+                    } else {
+                        last
+                    },
+                ),
+            });
+        }
+        ends
+    }
+
+    /// Token starting first in `line`, the longest one at its offset.
+    pub fn first(&self, line: &str) -> Option<Match> {
+        self.ends(line).map(|(first, _)| first)
+    }
+
+    /// Token starting last in `line`, the longest one at its offset.
+    pub fn last(&self, line: &str) -> Option<Match> {
+        self.ends(line).map(|(_, last)| last)
+    }
+
+    /// Value made of the first and the last token of a line.
+    pub fn calibration(&self, line: &str) -> Result<u32> {
+        value(self.ends(line)).ok_or_else(|| Error::new("no digits in line"))
+    }
+
+    /// Sum of calibration values of all lines of `document`.
+    pub fn total(&self, document: &str) -> Result<u64> {
+        let mut sum: u64 = 0;
+        for (i, line) in document.lines().enumerate() {
+            sum += u64::from(self.calibration(line).map_err(|err| err.at_line(i, line))?);
+        }
+        Ok(sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+    const EXAMPLE2: &str = include_str!("../files/example2.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day1::solve1(EXAMPLE), Ok(142));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day1::solve2(EXAMPLE2), Ok(281));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day1>(2000);
+    }
+
+    #[test]
+    fn test_calibration() {
+        let english = Dictionary::english();
+        assert_eq!(english.calibration("xtwone3four"), Ok(24));
+        assert_eq!(english.calibration("eightwo"), Ok(82));
+        // Offsets are in bytes, not characters.
+        assert_eq!(english.calibration("ü7twoü"), Ok(72));
+        assert_eq!(
+            english.last("atwone"),
+            Some(Match {
+                offset: 3,
+                len: 3,
+                value: 1
+            })
+        );
+
+        let err = english.calibration("abc").unwrap_err();
+        assert_eq!(err.message(), "no digits in line");
+        let err = Dictionary::digits().total("1abc2\nsix\n").unwrap_err();
+        assert_eq!(err.location().unwrap().line, 2);
+    }
+
+    #[test]
+    fn test_dictionaries() {
+        let mut german = Dictionary::digits();
+        german.add_words(&words::GERMAN);
+        assert_eq!(german.calibration("zweiundvierzig"), Ok(24));
+        assert_eq!(german.calibration("fünfxx"), Ok(55));
+
+        let mut french = Dictionary::default();
+        french.add_words(&words::FRENCH);
+        assert_eq!(french.calibration("troisdeux7"), Ok(32));
+
+        // The longest token wins at the same offset.
+        let mut roman = Dictionary::default();
+        roman.add_words(&words::ROMAN);
+        assert_eq!(roman.calibration("xVIIy"), Ok(71));
+        assert_eq!(roman.calibration("IX"), Ok(99));
+
+        let mut zero = Dictionary::english();
+        zero.add("zero", 0).unwrap();
+        assert_eq!(zero.calibration("zero5"), Ok(5));
+        assert_eq!(zero.calibration("3zero"), Ok(30));
+
+        assert_eq!(zero.add("", 1).unwrap_err().message(), "empty token");
+        assert_eq!(
+            zero.add("ten", 10).unwrap_err().message(),
+            "value 10 of token \"ten\" is not a digit"
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        testing::stream::<Day1>(2000);
+
+        let err = stream::solve::<Day1>("1abc2\nabc\n".as_bytes()).unwrap_err();
+        assert_eq!(err.message(), "no digits in line");
+        assert_eq!(err.location().unwrap().line, 2);
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let english = Dictionary::english();
+        assert_eq!(english.calibration("twone"), Ok(21));
+        assert_eq!(english.calibration("oneight"), Ok(18));
+        assert_eq!(english.calibration("sevenine"), Ok(79));
+        assert_eq!(english.calibration("xeightwothree"), Ok(83));
+
+        let mut dictionary = Dictionary::default();
+        dictionary
+            .add_all(&[("ab", 1), ("abc", 2), ("bc", 3), ("c", 4)])
+            .unwrap();
+        assert_eq!(dictionary.calibration("abc"), Ok(24));
+        // Nothing is added when a token is invalid.
+        assert!(dictionary.add_all(&[("d", 5), ("", 6)]).is_err());
+        assert_eq!(
+            dictionary.calibration("d").unwrap_err().message(),
+            "no digits in line"
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = Day1::parse("two1nine\n7pqrst\nxyz\n").unwrap();
+
+        assert_eq!(
+            Day1::explain(&input),
+            "line 1: two1nine\n\
+             \x20 part 1: first \"1\" at 3, last \"1\" at 3 -> 11\n\
+             \x20 part 2: first \"two\" at 0, last \"nine\" at 4 -> 29  <- parts disagree\n\
+             line 2: 7pqrst\n\
+             \x20 part 1: first \"7\" at 0, last \"7\" at 0 -> 77\n\
+             \x20 part 2: first \"7\" at 0, last \"7\" at 0 -> 77\n\
+             line 3: xyz\n\
+             \x20 part 1: no digits\n\
+             \x20 part 2: no digits\n\
+             1 of 3 lines differ between the parts\n"
+        );
+    }
+
+    /// Longest token at each offset, scanning from both ends.
+    fn naive(dictionary: &Dictionary, line: &str) -> Option<(Match, Match)> {
+        let at = |offset: usize| {
+            dictionary
+                .tokens
+                .iter()
+                .filter(|(token, _)| line[offset..].starts_with(token.as_str()))
+                .max_by_key(|(token, _)| token.len())
+                .map(|(token, value)| Match {
+                    offset,
+                    len: token.len(),
+                    value: *value,
+                })
+        };
+        let first = line.char_indices().find_map(|(offset, _)| at(offset))?;
+        let last = line
+            .char_indices()
+            .rev()
+            .find_map(|(offset, _)| at(offset))?;
+        Some((first, last))
+    }
+
+    proptest! {
+        #[test]
+        fn test_ends(line in "[onetwhrfuivsxg0-9ü]{0,30}") {
+            let english = Dictionary::english();
+            prop_assert_eq!(english.ends(&line), naive(&english, &line));
+        }
+
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day1::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::text()) {
+            prop_assert_eq!(Day1::parse(&s).unwrap().0, s);
+        }
+    }
+}
@@ -0,0 +1,583 @@
+use common::{
+    error::{Error, Result},
+    generate::{self, Rng},
+    render::{Canvas, Style},
+    solution::Solution,
+};
+use grid::{Grid, Pos};
+use std::collections::BTreeSet;
+
+/// Field of pipes.
+pub type Input = Map;
+
+/// Day 10: Pipe Maze.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        Grid::parse_with(input, |c| "|-LJ7F.S".contains(c).then_some(c))
+    }
+
+    fn part1(map: &Input) -> Result<usize> {
+        let (steps, _) = find_loop(map)?;
+
+        Ok(steps)
+    }
+
+    fn part2(map: &Input) -> Result<usize> {
+        let (_, seen) = find_loop(map)?;
+
+        // Crossings of the loop are told apart by the shapes of pipes, so the
+        // start gets the shape of the pipe under it.
+        let start = map.find_start()?;
+        let pipe = start_pipe(map)?;
+        if pipe == 'S' {
+            return Err(broken_loop());
+        }
+        let mut map = map.clone();
+        map[Pos::new(start.row, start.col)] = pipe;
+        let seen: BTreeSet<Point> = seen
+            .into_iter()
+            .map(|p| {
+                if p == start {
+                    Point::new(p.row, p.col, pipe)
+                } else {
+                    p
+                }
+            })
+            .collect();
+
+        let mut res = 0;
+        for p in map
+            .positions()
+            .filter_map(|pos| map.get_point_at(pos.row, pos.col))
+        {
+            if map.is_inside(&seen, &p)? {
+                res += 1;
+            }
+        }
+        Ok(res)
+    }
+
+    /// Grid of `size` x `size` with the main loop running around a random
+    /// shape without holes, other tiles are random pipes and ground.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        generate_map(size.max(2), rng).0.to_string()
+    }
+
+    /// Main loop drawn with box drawing characters.
+    fn visualize(map: &Input) -> Result<Canvas> {
+        let (_, seen) = find_loop(map)?;
+        let mut canvas = map.canvas();
+
+        for p in &seen {
+            let ch = match p.char {
+                '|' => '│',
+                '-' => '─',
+                'L' => '└',
+                'J' => '┘',
+                '7' => '┐',
+                'F' => '┌',
+                ch => ch,
+            };
+            canvas.draw((p.row, p.col), ch, Style::Path);
+        }
+        let start = map.find_start()?;
+        canvas.mark((start.row, start.col), Style::Highlight);
+
+        Ok(canvas)
+    }
+}
+
+/// Farthest distance from the start along the loop and all points of the loop.
+pub fn find_loop(map: &Map) -> Result<(usize, BTreeSet<Point>)> {
+    let mut queue: BTreeSet<Point> = BTreeSet::new();
+    let mut seen: BTreeSet<Point> = BTreeSet::new();
+    let mut steps: usize = 0;
+    queue.insert(map.find_start()?);
+
+    loop {
+        let mut new_points = Vec::new();
+
+        while let Some(p) = queue.pop_first() {
+            let connected = map.find_connected(&p);
+            seen.insert(p);
+            for q in &connected {
+                if !seen.contains(q) {
+                    new_points.push(*q);
+                }
+            }
+        }
+
+        if new_points.is_empty() {
+            break;
+        }
+
+        queue.extend(new_points.clone());
+        steps += 1;
+    }
+
+    Ok((steps, seen))
+}
+
+/// Pipe which the start stands for, judged by the pipes connected to it. "S"
+/// if there aren't exactly two of them.
+pub fn start_pipe(map: &Map) -> Result<char> {
+    let start = map.find_start()?;
+    let connected = map.find_connected(&start);
+    let up = connected.iter().any(|p| p.row < start.row);
+    let down = connected.iter().any(|p| p.row > start.row);
+    let left = connected.iter().any(|p| p.col < start.col);
+    let right = connected.iter().any(|p| p.col > start.col);
+
+    Ok(match (connected.len(), up, down, left, right) {
+        (2, true, true, _, _) => '|',
+        (2, _, _, true, true) => '-',
+        (2, true, _, true, _) => 'J',
+        (2, true, _, _, true) => 'L',
+        (2, _, true, true, _) => '7',
+        (2, _, true, _, true) => 'F',
+        _ => 'S',
+    })
+}
+
+/// Map of `size` x `size` tiles, which are the corners of `size - 1` x
+/// `size - 1` cells, and the number of tiles enclosed by the loop. The outline
+/// of a random shape of cells is the loop.
+fn generate_map(size: usize, rng: &mut Rng) -> (Map, usize) {
+    let shape = generate::polyomino(rng, size - 1, size - 1);
+
+    // Tiles are corners of cells, a side between a cell of the shape and one
+    // outside of it is a part of the loop.
+    let cell = |row: usize, col: usize, dr: isize, dc: isize| {
+        let (row, col) = (row as isize + dr, col as isize + dc);
+        row >= 0
+            && col >= 0
+            && shape.get(row as usize).and_then(|r| r.get(col as usize)) == Some(&true)
+    };
+    let mut map: Map = Grid::filled(size, size, '.');
+    let mut on_loop = Vec::new();
+    let mut enclosed = 0;
+    for pos in map.positions().collect::<Vec<_>>() {
+        let (row, col) = (pos.row, pos.col);
+        let up = cell(row, col, -1, -1) != cell(row, col, -1, 0);
+        let down = cell(row, col, 0, -1) != cell(row, col, 0, 0);
+        let left = cell(row, col, -1, -1) != cell(row, col, 0, -1);
+        let right = cell(row, col, -1, 0) != cell(row, col, 0, 0);
+
+        map[pos] = match (up, down, left, right) {
+            (true, true, false, false) => '|',
+            (false, false, true, true) => '-',
+            (true, false, true, false) => 'J',
+            (true, false, false, true) => 'L',
+            (false, true, true, false) => '7',
+            (false, true, false, true) => 'F',
+            _ => *rng.pick(&['.', '.', '|', '-', 'L', 'J', '7', 'F']),
+        };
+        if up || down || left || right {
+            on_loop.push(pos);
+        } else if cell(row, col, 0, 0) {
+            // All cells around the tile are in the shape.
+            enclosed += 1;
+        }
+    }
+
+    // Pipes around the start which aren't a part of the loop could connect to
+    // it as well.
+    let start = *rng.pick(&on_loop);
+    for pos in map.neighbours(start).collect::<Vec<_>>() {
+        if !on_loop.contains(&pos) {
+            map[pos] = '.';
+        }
+    }
+    map[start] = 'S';
+
+    (map, enclosed)
+}
+
+/// Field of pipes.
+pub type Map = Grid<char>;
+
+/// Following pipes of the map.
+pub trait Pipes {
+    fn find_start(&self) -> Result<Point>;
+    fn find_connected(&self, p: &Point) -> Vec<Point>;
+    fn get_char_at(&self, row: usize, col: usize) -> Option<char>;
+    fn get_point_at(&self, row: usize, col: usize) -> Option<Point>;
+    fn is_inside(&self, points: &BTreeSet<Point>, p: &Point) -> Result<bool>;
+}
+
+impl Pipes for Map {
+    fn find_start(&self) -> Result<Point> {
+        let pos = self.position(|&c| c == 'S').ok_or("no start")?;
+        Ok(Point::new(pos.row, pos.col, 'S'))
+    }
+
+    fn find_connected(&self, p: &Point) -> Vec<Point> {
+        let mut res: Vec<Point> = Vec::new();
+
+        if p.col > 0 {
+            if let Some(c) = self.get_char_at(p.row, p.col - 1) {
+                if let ('S' | '-' | 'J' | '7', '-' | 'F' | 'L') = (p.char, c) {
+                    res.push(Point::new(p.row, p.col - 1, c))
+                }
+            }
+        }
+        if p.col < self.cols() - 1 {
+            if let Some(c) = self.get_char_at(p.row, p.col + 1) {
+                if let ('S' | '-' | 'F' | 'L', '-' | '7' | 'J') = (p.char, c) {
+                    res.push(Point::new(p.row, p.col + 1, c))
+                }
+            }
+        }
+        if p.row > 0 {
+            if let Some(c) = self.get_char_at(p.row - 1, p.col) {
+                if let ('S' | '|' | 'L' | 'J', '|' | '7' | 'F') = (p.char, c) {
+                    res.push(Point::new(p.row - 1, p.col, c))
+                }
+            }
+        }
+        if p.row < self.rows() - 1 {
+            if let Some(c) = self.get_char_at(p.row + 1, p.col) {
+                if let ('S' | '|' | '7' | 'F', '|' | 'L' | 'J') = (p.char, c) {
+                    res.push(Point::new(p.row + 1, p.col, c))
+                }
+            }
+        }
+
+        res
+    }
+
+    fn get_char_at(&self, row: usize, col: usize) -> Option<char> {
+        self.get(Pos::new(row, col)).copied()
+    }
+
+    fn get_point_at(&self, row: usize, col: usize) -> Option<Point> {
+        Some(Point::new(row, col, self.get_char_at(row, col)?))
+    }
+
+    fn is_inside(&self, points: &BTreeSet<Point>, p: &Point) -> Result<bool> {
+        if points.contains(p) {
+            return Ok(false);
+        }
+
+        let directions = [p.col, p.row, self.cols() - p.col, self.rows() - p.row];
+        let m = directions.iter().enumerate().min_by_key(|x| x.1).unwrap();
+
+        let mut n = 0;
+        let mut path: Vec<Point> = Vec::new();
+
+        match m {
+            (0, _) => {
+                for col in 0..p.col {
+                    if let Some(q) = self.get_point_at(p.row, col) {
+                        if points.contains(&q) {
+                            if let Some(r) = path.last() {
+                                match (r.char, q.char) {
+                                    ('S' | '-' | 'F' | 'L', '-' | '7' | 'J') => {
+                                        path.push(q);
+                                        continue;
+                                    }
+                                    _ => {
+                                        match path.as_slice() {
+                                            [first, .., last] => match (first.char, last.char) {
+                                                ('F', '7') | ('L', 'J') => n += 2,
+                                                ('L', '7') | ('F', 'J') => n += 1,
+                                                _ => return Err(broken_loop()),
+                                            },
+                                            [_] => n += 1,
+                                            _ => {}
+                                        }
+                                        path.clear();
+                                        path.push(q);
+                                        continue;
+                                    }
+                                }
+                            } else {
+                                path.push(q)
+                            }
+                        }
+                    }
+                }
+                match path.as_slice() {
+                    [first, .., last] => match (first.char, last.char) {
+                        ('F', '7') | ('L', 'J') => n += 2,
+                        ('L', '7') | ('F', 'J') => n += 1,
+                        _ => return Err(broken_loop()),
+                    },
+                    [_] => n += 1,
+                    _ => {}
+                }
+            }
+            (1, _) => {
+                for row in 0..p.row {
+                    if let Some(q) = self.get_point_at(row, p.col) {
+                        if points.contains(&q) {
+                            if let Some(r) = path.last() {
+                                match (r.char, q.char) {
+                                    ('S' | '|' | '7' | 'F', '|' | 'L' | 'J') => {
+                                        path.push(q);
+                                        continue;
+                                    }
+                                    _ => {
+                                        match path.as_slice() {
+                                            [first, .., last] => match (first.char, last.char) {
+                                                ('F', 'L') | ('7', 'J') => n += 2,
+                                                ('F', 'J') | ('7', 'L') => n += 1,
+                                                _ => return Err(broken_loop()),
+                                            },
+                                            [_] => n += 1,
+                                            _ => {}
+                                        }
+                                        path.clear();
+                                        path.push(q);
+                                        continue;
+                                    }
+                                }
+                            } else {
+                                path.push(q);
+                            }
+                        }
+                    }
+                }
+                match path.as_slice() {
+                    [first, .., last] => match (first.char, last.char) {
+                        ('F', 'L') | ('7', 'J') => n += 2,
+                        ('F', 'J') | ('7', 'L') => n += 1,
+                        _ => return Err(broken_loop()),
+                    },
+                    [_] => n += 1,
+                    _ => {}
+                }
+            }
+            (2, _) => {
+                path.clear();
+                for col in p.col + 1..self.cols() {
+                    if let Some(q) = self.get_point_at(p.row, col) {
+                        if points.contains(&q) {
+                            if let Some(r) = path.last() {
+                                match (r.char, q.char) {
+                                    ('S' | '-' | 'F' | 'L', '-' | '7' | 'J') => {
+                                        path.push(q);
+                                        continue;
+                                    }
+                                    _ => {
+                                        match path.as_slice() {
+                                            [first, .., last] => match (first.char, last.char) {
+                                                ('F', '7') | ('L', 'J') => n += 2,
+                                                ('L', '7') | ('F', 'J') => n += 1,
+                                                _ => return Err(broken_loop()),
+                                            },
+                                            [_] => n += 1,
+                                            _ => {}
+                                        }
+                                        path.clear();
+                                        path.push(q);
+                                        continue;
+                                    }
+                                }
+                            } else {
+                                path.push(q)
+                            }
+                        }
+                    }
+                }
+                match path.as_slice() {
+                    [first, .., last] => match (first.char, last.char) {
+                        ('F', '7') | ('L', 'J') => n += 2,
+                        ('L', '7') | ('F', 'J') => n += 1,
+                        _ => return Err(broken_loop()),
+                    },
+                    [_] => n += 1,
+                    _ => {}
+                }
+            }
+            _ => {
+                for row in p.row + 1..self.rows() {
+                    if let Some(q) = self.get_point_at(row, p.col) {
+                        if points.contains(&q) {
+                            if let Some(r) = path.last() {
+                                match (r.char, q.char) {
+                                    ('S' | '|' | '7' | 'F', '|' | 'L' | 'J') => {
+                                        path.push(q);
+                                        continue;
+                                    }
+                                    _ => {
+                                        match path.as_slice() {
+                                            [first, .., last] => match (first.char, last.char) {
+                                                ('F', 'L') | ('7', 'J') => n += 2,
+                                                ('F', 'J') | ('7', 'L') => n += 1,
+                                                _ => return Err(broken_loop()),
+                                            },
+                                            [_] => n += 1,
+                                            _ => {}
+                                        }
+                                        path.clear();
+                                        path.push(q);
+                                        continue;
+                                    }
+                                }
+                            } else {
+                                path.push(q);
+                            }
+                        }
+                    }
+                }
+                match path.as_slice() {
+                    [first, .., last] => match (first.char, last.char) {
+                        ('F', 'L') | ('7', 'J') => n += 2,
+                        ('F', 'J') | ('7', 'L') => n += 1,
+                        _ => return Err(broken_loop()),
+                    },
+                    [_] => n += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(n % 2 == 1)
+    }
+}
+
+/// Error about pipes connected to the start which aren't a closed loop.
+fn broken_loop() -> Error {
+    Error::new("pipes from the start don't form a loop")
+}
+
+/// Tile of the map with its pipe.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+    pub char: char,
+}
+
+impl Point {
+    pub fn new(row: usize, col: usize, char: char) -> Point {
+        Point { row, col, char }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{render::Format, testing};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+    const EXAMPLE2: &str = include_str!("../files/example2.txt");
+
+    #[test]
+    fn test_map() {
+        let map_str = r#"
+            .....
+            .S-7.
+            .|.|.
+            .L-J.
+            .....
+"#;
+
+        let map: Map = map_str
+            .lines()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.trim())
+            .collect::<Vec<&str>>()
+            .join("\n")
+            .parse()
+            .unwrap();
+
+        assert_eq!(map.cols(), 5);
+        assert_eq!(map.rows(), 5);
+
+        assert_eq!(map.find_start(), Ok(Point::new(1, 1, 'S')));
+
+        assert_eq!(
+            map.find_connected(&map.find_start().unwrap()),
+            vec![Point::new(1, 2, '-'), Point::new(2, 1, '|')],
+        );
+
+        assert_eq!(
+            map.find_connected(&Point::new(3, 2, '-')),
+            vec![Point::new(3, 1, 'L'), Point::new(3, 3, 'J')],
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day10::solve1(EXAMPLE), Ok(8));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let canvas = Day10::visualize(&Day10::parse(EXAMPLE).unwrap()).unwrap();
+
+        assert_eq!(
+            canvas.render(Format::Ascii),
+            testing::lines(&["..┌┐.", ".┌┘│.", "S┘.└┐", "│┌──┘", "└┘..."])
+        );
+        assert_eq!(canvas.style((2, 0)), Some(Style::Highlight));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day10::solve2(EXAMPLE2), Ok(10));
+        // The start is a part of a straight run of pipes.
+        assert_eq!(Day10::solve2(EXAMPLE), Ok(1));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let err = Day10::parse("S-7\n|.x\n").unwrap_err();
+        assert_eq!(err.message(), "invalid cell");
+        assert_eq!(err.location().unwrap().line, 2);
+        assert_eq!(err.location().unwrap().column, 3);
+
+        assert_eq!(
+            Day10::solve2("S-7\n..|\n").unwrap_err().message(),
+            "pipes from the start don't form a loop"
+        );
+        assert_eq!(
+            Day10::solve1("F-7\n|.|\nL-J\n").unwrap_err().message(),
+            "no start"
+        );
+        assert_eq!(Day10::parse("\n").unwrap_err().message(), "empty grid");
+    }
+
+    #[test]
+    fn test_start_pipe() {
+        assert_eq!(start_pipe(&Day10::parse(EXAMPLE).unwrap()), Ok('F'));
+        assert_eq!(start_pipe(&Day10::parse(EXAMPLE2).unwrap()), Ok('7'));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day10>(140);
+
+        // Part 2 counts the tiles inside of the generated shape.
+        for seed in 0..200 {
+            let size = 2 + seed as usize % 20;
+            let (map, enclosed) = generate_map(size, &mut generate::Rng::new(seed));
+            let map: Map = map.to_string().parse().unwrap();
+
+            assert_eq!(Day10::part2(&map), Ok(enclosed), "seed {}\n{}", seed, map);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day10::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::grid("|-LJ7F.S", 10)) {
+            prop_assert_eq!(Day10::parse(&s).unwrap().to_string(), s);
+        }
+    }
+}
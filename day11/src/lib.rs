@@ -0,0 +1,198 @@
+use common::{
+    error::{Error, Result},
+    generate::Rng,
+    parallel,
+    solution::Solution,
+};
+use std::{
+    collections::BTreeSet,
+    io::{BufRead, BufReader, Read},
+};
+
+/// Observed image of the universe.
+pub type Input = Universe;
+
+/// Day 11: Cosmic Expansion.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        Universe::from_buf(input.as_bytes())
+    }
+
+    /// Image of `size` x `size` with sparse galaxies, some rows and columns
+    /// are left empty.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let empty_rows: Vec<bool> = (0..size).map(|_| rng.chance(0.1)).collect();
+        let empty_cols: Vec<bool> = (0..size).map(|_| rng.chance(0.1)).collect();
+
+        let mut res = String::new();
+        for row in &empty_rows {
+            for col in &empty_cols {
+                res.push(if !row && !col && rng.chance(0.02) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            res.push('\n');
+        }
+        res
+    }
+
+    fn part1(universe: &Input) -> Result<usize> {
+        Ok(solve(universe, 2))
+    }
+
+    fn part2(universe: &Input) -> Result<usize> {
+        Ok(solve(universe, 1000000))
+    }
+}
+
+/// Sum of distances between all pairs of galaxies after expansion.
+pub fn solve(universe: &Universe, expansion_coeff: usize) -> usize {
+    let galaxies = universe.expand(expansion_coeff);
+    let n = galaxies.len();
+
+    // Each galaxy sums up distances to the ones after it.
+    parallel::map_range(0..n, |i| {
+        let a = galaxies[i];
+        galaxies[i + 1..]
+            .iter()
+            .map(|&b| Universe::distance(a, b))
+            .sum::<usize>()
+    })
+    .into_iter()
+    .sum()
+}
+
+/// Galaxies and the empty rows and columns between them.
+#[derive(Debug)]
+pub struct Universe {
+    /// Positions of galaxies as (row, column).
+    pub galaxies: Vec<(usize, usize)>,
+    pub empty_rows: BTreeSet<usize>,
+    pub empty_cols: BTreeSet<usize>,
+}
+
+impl Universe {
+    fn from_buf<R: Read>(buf: R) -> Result<Self> {
+        let lines = BufReader::new(buf).lines();
+        let mut rows: usize = 0;
+        let mut cols: usize = 0;
+        let mut galaxies = Vec::new();
+
+        for (row, line) in lines.enumerate() {
+            let line = line?;
+            if row == 0 {
+                cols = line.chars().count();
+            }
+
+            for (col, ch) in line.chars().enumerate() {
+                if ch == '#' {
+                    galaxies.push((row, col))
+                }
+            }
+            rows += 1;
+        }
+
+        if rows == 0 {
+            return Err(Error::new("empty input"));
+        }
+
+        let mut empty_rows: BTreeSet<_> = (0..rows).collect();
+        let mut empty_cols: BTreeSet<_> = (0..cols).collect();
+
+        for (row, col) in &galaxies {
+            empty_rows.remove(row);
+            empty_cols.remove(col);
+        }
+
+        Ok(Universe {
+            galaxies,
+            empty_rows,
+            empty_cols,
+        })
+    }
+
+    /// Positions of galaxies after each empty row and column is replaced
+    /// with `expansion_coeff` of them.
+    pub fn expand(&self, expansion_coeff: usize) -> Vec<(usize, usize)> {
+        self.galaxies
+            .iter()
+            .map(|&(row, col)| {
+                (
+                    row + (expansion_coeff - 1) * self.empty_rows.range(..row).count(),
+                    col + (expansion_coeff - 1) * self.empty_cols.range(..col).count(),
+                )
+            })
+            .collect()
+    }
+
+    /// Manhattan distance between positions.
+    pub fn distance(a: (usize, usize), b: (usize, usize)) -> usize {
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day11::solve1(EXAMPLE), Ok(374));
+    }
+
+    #[test]
+    fn test_part2() {
+        // Answer for the real expansion is not published, only for smaller ones.
+        let universe = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(solve(&universe, 10), 1030);
+        assert_eq!(solve(&universe, 100), 8410);
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day11>(140);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day11::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::grid("#.", 10)) {
+            let universe = Day11::parse(&s).unwrap();
+
+            let lines: Vec<&str> = s.lines().collect();
+            let mut galaxies = Vec::new();
+            for (row, line) in lines.iter().enumerate() {
+                for (col, ch) in line.chars().enumerate() {
+                    if ch == '#' {
+                        galaxies.push((row, col));
+                    }
+                }
+            }
+            let empty_rows: BTreeSet<usize> = (0..lines.len()).filter(|&r| !lines[r].contains('#')).collect();
+            let empty_cols: BTreeSet<usize> =
+                (0..lines[0].len()).filter(|&c| lines.iter().all(|l| l.as_bytes()[c] == b'.')).collect();
+
+            prop_assert_eq!(universe.galaxies, galaxies);
+            prop_assert_eq!(universe.empty_rows, empty_rows);
+            prop_assert_eq!(universe.empty_cols, empty_cols);
+        }
+    }
+}
@@ -0,0 +1,439 @@
+use common::{
+    check::Check,
+    error::{self, Error, Result},
+    generate::Rng,
+    parallel,
+    solution::Solution,
+    stream::{self, Streaming},
+};
+use std::{io::BufRead, str::FromStr};
+
+/// Condition records, one per line.
+pub struct Input(pub Vec<Record>);
+
+/// Day 12: Hot Springs.
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(
+            stream::lines::<Self, _>(input.as_bytes()).collect::<Result<_>>()?,
+        ))
+    }
+
+    /// `size` records of up to 40 springs, which is longer than a 32 bit
+    /// mask. Up to 14 springs are unknown to keep trying all their states in
+    /// part 1 feasible.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut res = String::new();
+        for _ in 0..size {
+            let mut springs: Vec<char> = (0..rng.usize(1..41))
+                .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+                .collect();
+            if !springs.contains(&'#') {
+                let i = rng.usize(0..springs.len());
+                springs[i] = '#';
+            }
+            let groups: Vec<String> = springs
+                .split(|&ch| ch == '.')
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len().to_string())
+                .collect();
+
+            let mut unknown = 0;
+            for ch in springs.iter_mut() {
+                if unknown < 14 && rng.chance(0.4) {
+                    *ch = '?';
+                    unknown += 1;
+                }
+            }
+
+            let springs: String = springs.into_iter().collect();
+            res += &format!("{} {}\n", springs, groups.join(","));
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        part1::solve(&input.0)
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        Ok(part2::solve(&input.0))
+    }
+
+    /// Part 1 by trying all states of unknown springs against the memoized
+    /// count of part 2 on records which aren't unfolded.
+    fn cross_check(input: &Input) -> Result<Vec<Check>> {
+        let memoized: u64 = input.0.iter().map(part2::count_folded).sum();
+
+        Ok(vec![Check::new(
+            1,
+            "bitmask vs memoized",
+            part1::solve(&input.0)?,
+            memoized,
+        )])
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Result<(u64, u64)> {
+        stream::solve::<Self>(reader)
+    }
+}
+
+impl Streaming for Day12 {
+    type Line = Record;
+    /// Sums of arrangements of both parts.
+    type State = (u64, u64);
+
+    fn parse_line(line: &str) -> Result<Record> {
+        line.parse()
+    }
+
+    fn push(sums: &mut (u64, u64), record: &Record) -> Result<()> {
+        sums.0 += u64::from(part1::count(record)?);
+        sums.1 += part2::count_unfolded(record);
+        Ok(())
+    }
+
+    fn answers(sums: &(u64, u64)) -> (u64, u64) {
+        *sums
+    }
+}
+
+/// Springs with unknown state and sizes of damaged groups.
+#[derive(Debug)]
+pub struct Record {
+    pub springs: String,
+    pub groups: Vec<usize>,
+}
+
+impl FromStr for Record {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let (springs, right) = s
+            .split_once(' ')
+            .ok_or_else(|| Error::new("expected \"<springs> <groups>\"").with_text(s))?;
+
+        if let Some((i, ch)) = springs.char_indices().find(|(_, ch)| !".#?".contains(*ch)) {
+            return Err(Error::invalid("spring", &springs[i..i + ch.len_utf8()]));
+        }
+
+        let mut groups = Vec::new();
+        for ns in right.split(',') {
+            groups.push(error::parse(ns, "group size")?)
+        }
+
+        Ok(Record {
+            springs: springs.to_string(),
+            groups,
+        })
+    }
+}
+
+/// Counting arrangements of unfolded records with memoized recursion.
+pub mod part2 {
+
+    use super::*;
+    use core::hash::Hash;
+    use std::{
+        cmp,
+        collections::{hash_map::DefaultHasher, HashMap},
+        hash::Hasher,
+    };
+
+    pub fn solve(records: &[Record]) -> u64 {
+        parallel::map(records, count_unfolded).into_iter().sum()
+    }
+
+    /// Arrangements of a record repeated five times.
+    pub fn count_unfolded(record: &Record) -> u64 {
+        let line = Line::unfold(record);
+        Counter::new().count(&line.data, &line.checksum)
+    }
+
+    /// Arrangements of a record as written, without unfolding it.
+    pub fn count_folded(record: &Record) -> u64 {
+        let line = Line::new(record);
+        Counter::new().count(&line.data, &line.checksum)
+    }
+
+    fn hash_args<T: Hash + ?Sized, U: Hash + ?Sized>(a: &T, b: &U) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        a.hash(&mut hasher);
+        b.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    struct Counter {
+        cache: HashMap<u64, u64>,
+    }
+
+    impl Counter {
+        fn new() -> Self {
+            Counter {
+                cache: HashMap::new(),
+            }
+        }
+
+        fn count(&mut self, data: &[char], checksum: &[usize]) -> u64 {
+            let key = hash_args(data, checksum);
+
+            match self.cache.get(&key) {
+                Some(res) => *res,
+                None => {
+                    let res = match (data, checksum) {
+                        ([..], []) => {
+                            if data.contains(&'#') {
+                                0
+                            } else {
+                                1
+                            }
+                        }
+                        (_, [head, tail @ ..]) => {
+                            // Springs taken by the groups and a gap after
+                            // each, there are no arrangements if they don't fit.
+                            let needed = tail.iter().sum::<usize>() + head + tail.len();
+                            let max_offset = (data.len() + 1).saturating_sub(needed);
+                            let mut res = 0;
+                            for start in 0..max_offset {
+                                let end = start + head;
+
+                                if data[..start].contains(&'#') {
+                                    break;
+                                }
+
+                                if end <= data.len()
+                                    && !data[start..end].contains(&'.')
+                                    && data.get(end) != Some(&'#')
+                                {
+                                    res += self.count(&data[cmp::min(end + 1, data.len())..], tail)
+                                }
+                            }
+                            res
+                        }
+                    };
+                    self.cache.insert(key, res);
+                    res
+                }
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct Line {
+        data: Vec<char>,
+        checksum: Vec<usize>,
+    }
+
+    impl Line {
+        fn new(record: &Record) -> Self {
+            Line {
+                data: record.springs.chars().collect(),
+                checksum: record.groups.clone(),
+            }
+        }
+
+        /// Line with the record repeated five times.
+        fn unfold(record: &Record) -> Self {
+            let checksum = record.groups.repeat(5);
+
+            let mut data: Vec<char> = record.springs.chars().collect();
+            data.push('?');
+            data = data.repeat(5);
+            data.pop();
+
+            Line { data, checksum }
+        }
+    }
+}
+
+/// Counting arrangements by trying all states of unknown springs.
+pub mod part1 {
+    use super::*;
+
+    /// Most unknown springs of a record whose states fit a u64 mask.
+    pub const MAX_UNKNOWN: usize = 63;
+
+    pub fn solve(records: &[Record]) -> Result<u64> {
+        parallel::map(records, |record| count(record).map(u64::from))
+            .into_iter()
+            .sum()
+    }
+
+    /// Arrangements of a record, trying all states of its unknown springs.
+    pub fn count(record: &Record) -> Result<u32> {
+        let mut line = Line::new(record);
+        if line.unknown.len() > MAX_UNKNOWN {
+            return Err(
+                Error::new(format!("more than {} unknown springs", MAX_UNKNOWN))
+                    .with_text(&record.springs),
+            );
+        }
+        let mut res = 0;
+
+        for i in 0..1_u64 << line.unknown.len() {
+            for (j, &pos) in line.unknown.iter().enumerate() {
+                line.damaged[pos] = i & (1 << j) != 0;
+            }
+            if checksum(&line.damaged) == line.checksum {
+                res += 1;
+            }
+        }
+        Ok(res)
+    }
+
+    /// Sizes of groups of damaged springs.
+    fn checksum(damaged: &[bool]) -> Vec<usize> {
+        damaged
+            .split(|&d| !d)
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len())
+            .collect()
+    }
+
+    /// Record with damaged springs flagged, unknown springs are flagged by
+    /// each tried state.
+    #[derive(Debug)]
+    struct Line {
+        damaged: Vec<bool>,
+        checksum: Vec<usize>,
+        unknown: Vec<usize>,
+    }
+
+    impl Line {
+        fn new(record: &Record) -> Self {
+            Line {
+                damaged: record.springs.chars().map(|ch| ch == '#').collect(),
+                checksum: record.groups.clone(),
+                unknown: record
+                    .springs
+                    .chars()
+                    .enumerate()
+                    .filter(|(_, ch)| *ch == '?')
+                    .map(|(i, _)| i)
+                    .collect(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn damaged(s: &str) -> Vec<bool> {
+            s.chars().map(|ch| ch == '#').collect()
+        }
+
+        #[test]
+        fn test_checksum() {
+            assert_eq!(checksum(&damaged("###")), vec![3]);
+            assert_eq!(checksum(&damaged("######..#####.")), vec![6, 5]);
+            assert_eq!(checksum(&damaged("..#.##")), vec![1, 2]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day12::solve1(EXAMPLE), Ok(21));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day12::solve2(EXAMPLE), Ok(525152));
+    }
+
+    #[test]
+    fn test_part1_long() {
+        // Longer than 32 springs, the known ones are far from the end.
+        let line = format!("{}???.### 1,1,3", "#.".repeat(20));
+        let groups = ["1"; 20].join(",");
+        let input = line.replacen(" ", &format!(" {},", groups), 1);
+
+        assert_eq!(Day12::solve1(&input), Ok(1));
+        assert_eq!(Day12::solve1(&format!("{}?? 1", ".".repeat(40))), Ok(2));
+    }
+
+    #[test]
+    fn test_no_room() {
+        for record in ["? 1,1\n", "# 2\n"] {
+            assert_eq!(Day12::solve1(record), Ok(0));
+            assert_eq!(Day12::solve2(record), Ok(0));
+        }
+    }
+
+    #[test]
+    fn test_part1_invalid() {
+        assert_eq!(
+            Day12::parse("#é? 1\n").err().unwrap().message(),
+            "invalid spring"
+        );
+
+        let unknown = format!("{} 1", "?".repeat(part1::MAX_UNKNOWN + 1));
+        assert_eq!(
+            Day12::solve1(&unknown).unwrap_err().message(),
+            "more than 63 unknown springs"
+        );
+        assert!(Day12::solve2(&unknown).is_ok());
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day12>(50);
+    }
+
+    #[test]
+    fn test_stream() {
+        testing::stream::<Day12>(50);
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let checks = Day12::cross_check(&Day12::parse(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(checks, vec![Check::new(1, "bitmask vs memoized", 21, 21)]);
+
+        testing::cross_check::<Day12>(50);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day12::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            records in prop::collection::vec(("[.#?]{1,20}", prop::collection::vec(1..10_usize, 1..5)), 0..5)
+        ) {
+            let lines: Vec<String> = records
+                .iter()
+                .map(|(springs, groups)| {
+                    let groups: Vec<String> = groups.iter().map(usize::to_string).collect();
+                    format!("{} {}", springs, groups.join(","))
+                })
+                .collect();
+
+            let input = Day12::parse(&testing::lines(&lines)).unwrap();
+
+            prop_assert_eq!(input.0.len(), records.len());
+            for (record, (springs, groups)) in input.0.iter().zip(&records) {
+                prop_assert_eq!(&record.springs, springs);
+                prop_assert_eq!(&record.groups, groups);
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use common::{
+    error::{Error, Result},
+    generate::Rng,
+    solution::Solution,
+};
+use grid::{Grid, Pos};
+
+/// Patterns of ash and rocks.
+pub type Input = Vec<Map>;
+
+/// Day 13: Point of Incidence.
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        read_maps(input)
+    }
+
+    fn part1(maps: &Input) -> Result<usize> {
+        part1::solve(maps)
+    }
+
+    fn part2(maps: &Input) -> Result<usize> {
+        part2::solve(maps)
+    }
+
+    /// `size` patterns of up to 15 x 15, each with a single perfect
+    /// reflection and a single other one with exactly one smudge.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let patterns: Vec<String> = (0..size).map(|_| pattern(rng).to_string()).collect();
+        patterns.join("\n")
+    }
+}
+
+/// Pattern symmetric about a line between rows and about one between
+/// columns, with a single cell flipped in a row which the first line doesn't
+/// reflect. Tried again until no other line reflects it with at most one
+/// smudge.
+fn pattern(rng: &mut Rng) -> Map {
+    loop {
+        let (rows, cols) = (rng.usize(5..16), rng.usize(5..16));
+        let (row_line, col_line) = (rng.usize(1..rows), rng.usize(1..cols));
+        let mut map: Map = Grid::filled(rows, cols, '.');
+
+        for pos in map.positions().collect::<Vec<_>>() {
+            // Cells reflected by either line copy their mirror image.
+            let col = mirror(pos.col, col_line);
+            let row = mirror(pos.row, row_line);
+            map[pos] = if col < pos.col {
+                map[Pos::new(pos.row, col)]
+            } else if row < pos.row {
+                map[Pos::new(row, pos.col)]
+            } else if rng.chance(0.5) {
+                '#'
+            } else {
+                '.'
+            };
+        }
+
+        let depth = row_line.min(rows - row_line);
+        let free_rows: Vec<usize> = (0..rows)
+            .filter(|&r| r + depth < row_line || r >= row_line + depth)
+            .collect();
+        if free_rows.is_empty() {
+            continue;
+        }
+        let depth = col_line.min(cols - col_line);
+        let pos = Pos::new(*rng.pick(&free_rows), rng.usize(col_line - depth..col_line));
+        map[pos] = if map[pos] == '#' { '.' } else { '#' };
+
+        let transposed = map.transpose();
+        let smudges: Vec<usize> = (1..rows)
+            .map(|line| mismatches(&map, line))
+            .chain((1..cols).map(|line| mismatches(&transposed, line)))
+            .collect();
+        if smudges.iter().filter(|&&n| n == 0).count() == 1
+            && smudges.iter().filter(|&&n| n == 1).count() == 1
+        {
+            return map;
+        }
+    }
+}
+
+/// Index reflected about the line before index `line`, or the index itself if
+/// the reflection falls outside of `0..`.
+fn mirror(index: usize, line: usize) -> usize {
+    if index >= line && 2 * line > index {
+        2 * line - index - 1
+    } else {
+        index
+    }
+}
+
+/// Cells which differ from their reflection about the line before row `line`.
+fn mismatches(map: &Map, line: usize) -> usize {
+    let depth = line.min(map.rows() - line);
+    (0..depth)
+        .map(|i| {
+            let (a, b) = (map.row(line - i - 1), map.row(line + i));
+            a.iter().zip(b).filter(|(x, y)| x != y).count()
+        })
+        .sum()
+}
+
+fn read_maps(input: &str) -> Result<Vec<Map>> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    lines
+        .split(|line| line.is_empty())
+        .filter(|block| !block.is_empty())
+        .map(|block| block.join("\n").parse())
+        .collect()
+}
+
+/// Reflections with exactly one smudge fixed.
+pub mod part2 {
+    use std::{cmp::min, iter::zip};
+
+    use super::*;
+
+    pub fn solve(maps: &[Map]) -> Result<usize> {
+        let mut res: usize = 0;
+
+        for (i, map) in maps.iter().enumerate() {
+            if let Some(row) = find_offset(map) {
+                res += (row + 1) * 100;
+            } else if let Some(col) = find_offset(&map.transpose()) {
+                res += col + 1;
+            } else {
+                return Err(Error::new(format!(
+                    "no reflection with one smudge in pattern {}",
+                    i + 1
+                )));
+            }
+        }
+        Ok(res)
+    }
+
+    fn find_offset(map: &Map) -> Option<usize> {
+        for offset in 0..map.rows() - 1 {
+            let depth = min(offset + 1, map.rows() - offset - 1);
+            let mut diff = 0;
+            for i in 0..depth {
+                diff += zip(map.row(offset - i), map.row(offset + i + 1))
+                    .map(|(x, y)| if x == y { 0 } else { 1 })
+                    .sum::<usize>();
+            }
+            if diff == 1 {
+                return Some(offset);
+            }
+        }
+        None
+    }
+}
+
+/// Perfect reflections.
+pub mod part1 {
+    use super::*;
+    use std::{cmp::min, collections::HashSet};
+
+    pub fn solve(maps: &[Map]) -> Result<usize> {
+        let mut res: usize = 0;
+
+        for (i, map) in maps.iter().enumerate() {
+            let mut cols: HashSet<usize> = HashSet::new();
+
+            for (i, row) in map.iter_rows().enumerate() {
+                if i == 0 {
+                    cols = find_possible_reflection_offsets(row);
+                } else {
+                    cols.retain(|offset| check_reflection(row, *offset));
+                }
+            }
+
+            for col in cols.iter() {
+                res += col + 1;
+            }
+
+            let mut rows: HashSet<usize> = HashSet::new();
+
+            for (i, col) in map.transpose().iter_rows().enumerate() {
+                if i == 0 {
+                    rows = find_possible_reflection_offsets(col);
+                } else {
+                    rows.retain(|offset| check_reflection(col, *offset));
+                }
+            }
+
+            for row in rows.iter() {
+                res += (row + 1) * 100;
+            }
+
+            if cols.is_empty() && rows.is_empty() {
+                return Err(Error::new(format!("no reflection in pattern {}", i + 1)));
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn find_possible_reflection_offsets(s: &[char]) -> HashSet<usize> {
+        let mut offsets = HashSet::new();
+
+        for offset in 0..s.len() - 1 {
+            if check_reflection(s, offset) {
+                offsets.insert(offset);
+            }
+        }
+
+        offsets
+    }
+
+    fn check_reflection(s: &[char], offset: usize) -> bool {
+        let depth = min(offset + 1, s.len() - offset - 1);
+        for i in 0..depth {
+            if s[offset - i] != s[offset + i + 1] {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_check_reflection() {
+            assert!(check_reflection(
+                &"#.##..##.".chars().collect::<Vec<char>>(),
+                4
+            ));
+            assert!(check_reflection(
+                &"..##.###..###.##.".chars().collect::<Vec<char>>(),
+                0
+            ));
+        }
+
+        #[test]
+        fn test_find_possible_reflection_offsets() {
+            assert_eq!(
+                find_possible_reflection_offsets(&"#.##..##.".chars().collect::<Vec<char>>()),
+                HashSet::from_iter(vec![4, 6])
+            );
+            assert_eq!(
+                find_possible_reflection_offsets(
+                    &"..##.###..###.##.".chars().collect::<Vec<char>>()
+                ),
+                HashSet::from_iter(vec![0, 8, 14])
+            );
+        }
+    }
+}
+
+/// Pattern of ash "." and rocks "#".
+pub type Map = Grid<char>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day13::solve1(EXAMPLE), Ok(405));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day13::solve2(EXAMPLE), Ok(400));
+    }
+
+    #[test]
+    fn test_no_reflection() {
+        let input = format!("{}\n#\n", EXAMPLE);
+
+        assert_eq!(
+            Day13::solve1(&input).unwrap_err().message(),
+            "no reflection in pattern 3"
+        );
+        assert_eq!(
+            Day13::solve2(&input).unwrap_err().message(),
+            "no reflection with one smudge in pattern 3"
+        );
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let maps = Day13::parse(EXAMPLE).unwrap();
+
+        assert_eq!(mismatches(&maps[1], 4), 0);
+        assert_eq!(mismatches(&maps[1], 1), 1);
+        assert_eq!(mismatches(&maps[0].transpose(), 5), 0);
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day13>(100);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day13::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(maps in prop::collection::vec(testing::grid("#.", 10), 1..4)) {
+            let parsed: Vec<String> = Day13::parse(&maps.join("\n")).unwrap().iter().map(Map::to_string).collect();
+            prop_assert_eq!(parsed, maps);
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use common::{
+    error::{Error, Result},
+    generate::Rng,
+    render::{Canvas, Style},
+    solution::Solution,
+    trace::Trace,
+};
+use grid::Grid;
+use part2::Tilter;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Platform with rounded and cube-shaped rocks.
+pub type Input = Map;
+
+/// Day 14: Parabolic Reflector Dish.
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        Grid::parse_with(input, |c| "O#.".contains(c).then_some(c))
+    }
+
+    /// Platform of `size` x `size` with rounded and cube-shaped rocks.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut res = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                res.push(*rng.pick(&['O', 'O', '#', '.', '.', '.', '.', '.', '.']));
+            }
+            res.push('\n');
+        }
+        res
+    }
+
+    fn part1(map: &Input) -> Result<usize> {
+        part1::solve(map)
+    }
+
+    fn part2(map: &Input) -> Result<usize> {
+        part2::solve(&mut map.clone())
+    }
+
+    /// Platform tilted north as in part 1.
+    fn visualize(map: &Input) -> Result<Canvas> {
+        let mut tilted = map.clone();
+        tilted.tilt_north();
+
+        Ok(rocks(&tilted))
+    }
+
+    /// Every tilt of spin cycles of part 2 until the platform gets into a
+    /// state it has already been in.
+    fn trace(map: &Input, trace: &mut Trace) -> Result<()> {
+        let mut map = map.clone();
+        let mut seen = HashSet::new();
+        trace.record(rocks(&map));
+
+        while seen.insert(map.checksum()) {
+            for tilt in [
+                Map::tilt_north,
+                Map::tilt_west,
+                Map::tilt_south,
+                Map::tilt_east,
+            ] {
+                tilt(&mut map);
+                trace.record(rocks(&map));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Platform with round rocks highlighted.
+fn rocks(map: &Map) -> Canvas {
+    let mut canvas = map.canvas();
+    canvas.mark_all(
+        map.positions().filter(|&pos| map[pos] == 'O'),
+        Style::Highlight,
+    );
+    canvas
+}
+
+/// Load after a billion spin cycles, found by detecting a cycle of states.
+pub mod part2 {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    pub fn solve(map: &mut Map) -> Result<usize> {
+        let mut seen: HashMap<u64, usize> = HashMap::new();
+        let mut loop_start = 0;
+        let mut loop_len = 0;
+
+        for i in 0..1000000000 {
+            let hash = map.checksum();
+
+            if let Some(ind) = seen.get(&hash) {
+                loop_start = *ind;
+                loop_len = i - ind;
+                break;
+            } else {
+                seen.insert(hash, i);
+            }
+
+            map.tilt_north();
+            map.tilt_west();
+            map.tilt_south();
+            map.tilt_east();
+        }
+
+        let count = (1000000000 - loop_start) % loop_len;
+
+        for _ in 0..count {
+            map.tilt_north();
+            map.tilt_west();
+            map.tilt_south();
+            map.tilt_east();
+        }
+
+        Ok(map.load())
+    }
+
+    /// Rolls all round rocks as far as they go in a direction.
+    pub trait Tilter {
+        fn tilt_north(&mut self);
+        fn tilt_south(&mut self);
+        fn tilt_west(&mut self);
+        fn tilt_east(&mut self);
+    }
+
+    impl Tilter for Map {
+        fn tilt_north(&mut self) {
+            for i in 0..self.cols() {
+                tilt_col(self, i, false);
+            }
+        }
+
+        fn tilt_south(&mut self) {
+            for i in 0..self.cols() {
+                tilt_col(self, i, true);
+            }
+        }
+
+        fn tilt_west(&mut self) {
+            for i in 0..self.rows() {
+                tilt_row(self, i, false);
+            }
+        }
+
+        fn tilt_east(&mut self) {
+            for i in 0..self.rows() {
+                tilt_row(self, i, true);
+            }
+        }
+    }
+
+    fn tilt_row(map: &mut Map, index: usize, rev: bool) {
+        let row = tilt(map.row(index), rev);
+        map.row_mut(index).copy_from_slice(&row);
+    }
+
+    fn tilt_col(map: &mut Map, index: usize, rev: bool) {
+        let col = tilt(&map.col(index).copied().collect::<Vec<char>>(), rev);
+        map.col_mut(index).zip(col).for_each(|(c, v)| *c = v);
+    }
+
+    /// Cells rolled to the start, or to the end if `rev`. Cells other than
+    /// rounded rocks and empty space stay in place like cube-shaped rocks.
+    fn tilt(v: &[char], rev: bool) -> Vec<char> {
+        let mut res = v.to_vec();
+        let mut stop: usize = if rev { v.len() - 1 } else { 0 };
+
+        for i in 0..res.len() {
+            let ind = if rev { v.len() - 1 - i } else { i };
+            let c = res[ind];
+            match c {
+                'O' => {
+                    res.swap(ind, stop);
+                    stop = if rev {
+                        if stop > 0 {
+                            stop - 1
+                        } else {
+                            0
+                        }
+                    } else {
+                        stop + 1
+                    };
+                }
+                '.' => continue,
+                _ => {
+                    stop = if rev {
+                        if ind > 0 {
+                            ind - 1
+                        } else {
+                            0
+                        }
+                    } else {
+                        ind + 1
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_tilt() {
+            assert_eq!(
+                tilt(&"O.O#.OO...".chars().collect::<Vec<char>>(), false),
+                "OO.#OO....".chars().collect::<Vec<char>>()
+            );
+
+            assert_eq!(
+                tilt(&"O.O#.OO...".chars().collect::<Vec<char>>(), true),
+                ".OO#....OO".chars().collect::<Vec<char>>()
+            );
+        }
+    }
+}
+
+/// Load after tilting the platform north.
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(map: &Map) -> Result<usize> {
+        let mut res: usize = 0;
+
+        for col in map.iter_cols() {
+            let mut stop: usize = 0;
+            for (i, c) in col.enumerate() {
+                match c {
+                    'O' => {
+                        res += map.rows() - stop;
+                        stop += 1;
+                    }
+                    '.' => continue,
+                    '#' => stop = i + 1,
+                    _ => return Err(Error::invalid("cell", &c.to_string())),
+                }
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+/// Platform with round "O" and cube "#" rocks.
+pub type Map = Grid<char>;
+
+/// Measurements of the platform state.
+pub trait Platform {
+    fn load(&self) -> usize;
+    fn checksum(&self) -> u64;
+}
+
+impl Platform for Map {
+    fn load(&self) -> usize {
+        let mut res = 0;
+        for (i, row) in self.iter_rows().enumerate() {
+            res += (self.rows() - i) * row.iter().filter(|&&c| c == 'O').count()
+        }
+        res
+    }
+
+    fn checksum(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{render::Format, testing};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day14::solve1(EXAMPLE), Ok(136));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let err = Day14::parse("O.x\n...\n").unwrap_err();
+        assert_eq!(err.message(), "invalid cell");
+        assert_eq!(err.location().unwrap().line, 1);
+        assert_eq!(err.location().unwrap().column, 3);
+
+        let map: Map = Grid::filled(2, 2, 'x');
+        assert!(Day14::part1(&map).is_err());
+    }
+
+    #[test]
+    fn test_visualize() {
+        let canvas = Day14::visualize(&Day14::parse(EXAMPLE).unwrap()).unwrap();
+
+        assert!(canvas
+            .render(Format::Ascii)
+            .starts_with("OOOO.#.O..\nOO..#....#\nOO..O##..O\n"));
+        assert_eq!(canvas.style((0, 0)), Some(Style::Highlight));
+        assert_eq!(canvas.style((0, 4)), None);
+    }
+
+    #[test]
+    fn test_trace() {
+        let map = Day14::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(14);
+        Day14::trace(&map, &mut trace).unwrap();
+
+        // States repeat from the 3rd cycle with period 7.
+        let frames: Vec<Canvas> = trace.frames().collect();
+        assert_eq!(frames.len(), 1 + 4 * 10);
+        assert_eq!(frames[0], rocks(&map));
+        assert_eq!(frames[4 * 3], frames[4 * 10]);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day14::solve2(EXAMPLE), Ok(64));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day14>(50);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day14::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::grid("O#.", 10)) {
+            prop_assert_eq!(Day14::parse(&s).unwrap().to_string(), s);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use common::{
+    error::{self, Context, Error, Result},
+    generate::Rng,
+    solution::Solution,
+};
+use std::collections::HashMap;
+
+/// Initialization sequence.
+pub struct Input(pub String);
+
+/// Day 15: Lens Library.
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(read_sequence(input).to_string()))
+    }
+
+    /// `size` steps on a pool of labels, a quarter of them removals.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let labels: Vec<String> = (0..size / 4 + 1)
+            .map(|_| {
+                (0..rng.usize(1..7))
+                    .map(|_| (b'a' + rng.below(26) as u8) as char)
+                    .collect()
+            })
+            .collect();
+
+        let steps: Vec<String> = (0..size)
+            .map(|_| {
+                let label = rng.pick(&labels);
+                if rng.chance(0.25) {
+                    format!("{}-", label)
+                } else {
+                    format!("{}={}", label, rng.usize(1..10))
+                }
+            })
+            .collect();
+        steps.join(",") + "\n"
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        Ok(hash_seq(&input.0))
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        let s = input.0.as_str();
+
+        let mut boxes: HashMap<u64, Vec<(String, u64)>> = HashMap::new();
+
+        for cmd in s.split(',') {
+            if let Some(label) = cmd.strip_suffix('-') {
+                boxes
+                    .entry(hash(label))
+                    .and_modify(|e| e.retain(|(l, _)| l != label));
+            } else {
+                let (label, flen) = cmd
+                    .split_once('=')
+                    .ok_or_else(|| Error::invalid("step", cmd))
+                    .at_line(0, s)?;
+                let value = (
+                    label.to_string(),
+                    error::parse(flen, "focal length").at_line(0, s)?,
+                );
+
+                boxes
+                    .entry(hash(label))
+                    .and_modify(|e| {
+                        if let Some(element) = e.iter_mut().find(|x| x.0 == label) {
+                            *element = value.to_owned();
+                        } else {
+                            e.push(value.to_owned())
+                        }
+                    })
+                    .or_insert(vec![value]);
+            }
+        }
+
+        let mut res: u64 = 0;
+
+        for (bi, lenses) in &boxes {
+            for (i, (_, flen)) in lenses.iter().enumerate() {
+                res += (bi + 1) * (i as u64 + 1) * flen;
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+fn read_sequence(input: &str) -> &str {
+    input.trim_end()
+}
+
+/// Sum of hashes of comma separated steps.
+pub fn hash_seq(s: &str) -> u64 {
+    s.split(",").map(hash).sum()
+}
+
+/// HASH algorithm of a single step.
+pub fn hash(s: &str) -> u64 {
+    let mut state: u64 = 0;
+
+    for c in s.chars() {
+        state += (c as u8) as u64;
+        state *= 17;
+        state %= 256;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(hash("rn=1"), 30);
+        assert_eq!(hash("pc=6"), 214);
+        assert_eq!(hash("qp-"), 14);
+    }
+
+    #[test]
+    fn test_hash_seq() {
+        assert_eq!(
+            hash_seq("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"),
+            1320
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day15::solve1(EXAMPLE), Ok(1320));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day15::solve2(EXAMPLE), Ok(145));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day15>(2000);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day15::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in "[a-z]{1,6}(=[1-9]|-)(,[a-z]{1,6}(=[1-9]|-)){0,10}") {
+            prop_assert_eq!(Day15::parse(&(s.clone() + "\n")).unwrap().0, s);
+        }
+    }
+}
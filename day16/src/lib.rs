@@ -0,0 +1,282 @@
+use common::{
+    error::Result,
+    generate::Rng,
+    parallel,
+    render::{Canvas, Style},
+    solution::Solution,
+    trace::Trace,
+};
+use grid::{Direction, Grid, Pos};
+use std::{collections::HashSet, hash::Hash};
+
+/// Contraption with mirrors and splitters.
+pub type Input = Map;
+
+/// Day 16: The Floor Will Be Lava.
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        Grid::parse_with(input, |c| "./\\-|".contains(c).then_some(c))
+    }
+
+    /// Contraption of `size` x `size` with sparse mirrors and splitters.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut res = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                res.push(if rng.chance(0.1) {
+                    *rng.pick(&['/', '\\', '|', '-'])
+                } else {
+                    '.'
+                });
+            }
+            res.push('\n');
+        }
+        res
+    }
+
+    fn part1(map: &Input) -> Result<usize> {
+        Ok(count_energized(
+            map,
+            &Beam::new(Pos::new(0, 0), Direction::Right),
+        ))
+    }
+
+    fn part2(map: &Input) -> Result<usize> {
+        Ok(
+            parallel::map(&entry_points(map), |beam| count_energized(map, beam))
+                .into_iter()
+                .max()
+                .unwrap(),
+        )
+    }
+
+    /// Tiles energized by the beam of part 1 drawn as "#".
+    fn visualize(map: &Input) -> Result<Canvas> {
+        let start = Beam::new(Pos::new(0, 0), Direction::Right);
+        let seen = propagate(map, &start, |_, _| {});
+
+        Ok(draw(map, &start, &seen, &[]))
+    }
+
+    /// Beam of part 1 spreading a tile per frame.
+    fn trace(map: &Input, trace: &mut Trace) -> Result<()> {
+        let start = Beam::new(Pos::new(0, 0), Direction::Right);
+        trace.record(draw(map, &start, &HashSet::new(), &[start]));
+
+        propagate(map, &start, |seen, beams| {
+            trace.record(draw(map, &start, seen, beams))
+        });
+
+        Ok(())
+    }
+}
+
+/// Energized tiles drawn as "#" and heads of `beams` as arrows.
+fn draw(map: &Map, start: &Beam, seen: &HashSet<Beam>, beams: &[Beam]) -> Canvas {
+    let mut canvas = map.canvas();
+
+    for beam in seen {
+        if map[beam.pos] == '.' {
+            canvas.draw(beam.pos, '#', Style::Visited);
+        } else {
+            canvas.mark(beam.pos, Style::Visited);
+        }
+    }
+    canvas.mark(start.pos, Style::Highlight);
+
+    for beam in beams {
+        if map[beam.pos] == '.' {
+            canvas.draw(beam.pos, beam.direction.arrow(), Style::Path);
+        } else {
+            canvas.mark(beam.pos, Style::Path);
+        }
+    }
+
+    canvas
+}
+
+/// Contraption of mirrors and splitters.
+pub type Map = Grid<char>;
+
+/// All beams entering the contraption from its edges.
+pub fn entry_points(map: &Map) -> Vec<Beam> {
+    let mut res = Vec::new();
+    let rows = map.rows();
+    let cols = map.cols();
+
+    for col in 0..cols {
+        res.push(Beam::new(Pos::new(0, col), Direction::Down));
+        res.push(Beam::new(Pos::new(rows - 1, col), Direction::Up))
+    }
+
+    for row in 0..rows {
+        res.push(Beam::new(Pos::new(row, 0), Direction::Right));
+        res.push(Beam::new(Pos::new(row, cols - 1), Direction::Left));
+    }
+
+    res
+}
+
+/// Number of tiles energized by the beam entering at `start`.
+pub fn count_energized(map: &Map, start: &Beam) -> usize {
+    energized(map, start).len()
+}
+
+/// Tiles the beam entering at `start` passes through.
+pub fn energized(map: &Map, start: &Beam) -> HashSet<Pos> {
+    propagate(map, start, |_, _| {})
+        .iter()
+        .map(|beam| beam.pos)
+        .collect()
+}
+
+/// Moves the beam entering at `start` through the contraption a tile at a
+/// time until it only goes where it has already been, returns all states of
+/// the beam. `on_step` is called after each step with the states seen so far
+/// and the beams which are to move next.
+pub fn propagate<F>(map: &Map, start: &Beam, mut on_step: F) -> HashSet<Beam>
+where
+    F: FnMut(&HashSet<Beam>, &[Beam]),
+{
+    let mut seen: HashSet<Beam> = HashSet::new();
+    let mut beams: Vec<Beam> = vec![*start];
+
+    while !beams.is_empty() {
+        let mut next = Vec::new();
+        for beam in beams {
+            step(map, beam, &mut seen, &mut next);
+        }
+        next.retain(|beam| !seen.contains(beam));
+        beams = next;
+        on_step(&seen, &beams);
+    }
+
+    seen
+}
+
+/// Moves a single beam by one tile, the beams it continues as are added to
+/// `next`. Beams which were already seen are dropped.
+fn step(map: &Map, mut beam: Beam, seen: &mut HashSet<Beam>, next: &mut Vec<Beam>) {
+    if !seen.insert(beam) {
+        return;
+    }
+
+    match map[beam.pos] {
+        '.' => {}
+        '\\' => match beam.direction {
+            Direction::Up => beam.direction = Direction::Left,
+            Direction::Down => beam.direction = Direction::Right,
+            Direction::Left => beam.direction = Direction::Up,
+            Direction::Right => beam.direction = Direction::Down,
+        },
+        '/' => match beam.direction {
+            Direction::Up => beam.direction = Direction::Right,
+            Direction::Down => beam.direction = Direction::Left,
+            Direction::Left => beam.direction = Direction::Down,
+            Direction::Right => beam.direction = Direction::Up,
+        },
+        '-' => match beam.direction {
+            Direction::Left | Direction::Right => {}
+            Direction::Up | Direction::Down => {
+                beam.direction = Direction::Left;
+                next.push(Beam::new(beam.pos, Direction::Right));
+            }
+        },
+        '|' => match beam.direction {
+            Direction::Left | Direction::Right => {
+                beam.direction = Direction::Up;
+                next.push(Beam::new(beam.pos, Direction::Down));
+            }
+            Direction::Up | Direction::Down => {}
+        },
+        _ => unreachable!("map contains only valid tiles"),
+    }
+
+    if let Some(pos) = map.step(beam.pos, beam.direction) {
+        next.push(Beam::new(pos, beam.direction))
+    }
+}
+
+/// Beam at a tile heading in a direction.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Beam {
+    pub pos: Pos,
+    pub direction: Direction,
+}
+
+impl Beam {
+    pub fn new(pos: Pos, direction: Direction) -> Self {
+        Beam { pos, direction }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{render::Format, testing};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day16::solve1(EXAMPLE), Ok(46));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let map = Day16::parse(EXAMPLE).unwrap();
+        let canvas = Day16::visualize(&map).unwrap();
+
+        assert_eq!(
+            map.positions()
+                .filter(|&p| canvas.style(p.into()).is_some())
+                .count(),
+            46
+        );
+        assert!(canvas.render(Format::Ascii).starts_with("#|###\\....\n"));
+    }
+
+    #[test]
+    fn test_trace() {
+        let map = Day16::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(16);
+        Day16::trace(&map, &mut trace).unwrap();
+
+        let frames: Vec<Canvas> = trace.frames().collect();
+        assert!(frames.len() > 10);
+        assert_eq!(frames[1].get((0, 1)), Some('|'));
+        assert_eq!(frames[1].style((0, 1)), Some(Style::Path));
+        assert_eq!(frames.last(), Some(&Day16::visualize(&map).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day16::solve2(EXAMPLE), Ok(51));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day16>(30);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day16::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::grid("./\\-|", 10)) {
+            prop_assert_eq!(Day16::parse(&s).unwrap().to_string(), s);
+        }
+    }
+}
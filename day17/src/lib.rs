@@ -0,0 +1,292 @@
+use common::{
+    error::Result,
+    generate::Rng,
+    render::{Canvas, Style},
+    solution::Solution,
+};
+use grid::{Direction, Grid, Pos};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+/// Heat loss of each city block.
+pub type Input = Map;
+
+/// Day 17: Clumsy Crucible.
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Input> {
+        Grid::parse_with(input, |c| c.to_digit(10))
+    }
+
+    /// City of `size` x `size` blocks, at least 5 so that the ultra crucible
+    /// can reach the end, with heat losses of 1 to 9.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let size = size.max(5);
+        let mut res = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                res += &rng.usize(1..10).to_string();
+            }
+            res.push('\n');
+        }
+        res
+    }
+
+    fn part1(map: &Input) -> Result<u32> {
+        Ok(solve(map, 1, 3))
+    }
+
+    fn part2(map: &Input) -> Result<u32> {
+        Ok(solve(map, 4, 10))
+    }
+
+    /// Path of the crucible of part 1 drawn with arrows over heat losses.
+    fn visualize(map: &Input) -> Result<Canvas> {
+        let mut canvas = map.canvas();
+        let path = best_path(map, 1, 3);
+
+        for p in path.iter().skip(1) {
+            canvas.draw(p.pos, p.direction.arrow(), Style::Path);
+        }
+        if let Some(start) = path.first() {
+            canvas.mark(start.pos, Style::Highlight);
+        }
+
+        Ok(canvas)
+    }
+}
+
+/// Heat loss of each city block.
+pub type Map = Grid<u32>;
+
+/// Least heat loss from the top left to the bottom right block, with the crucible
+/// moving at least `min_moves` and at most `max_moves` blocks in a straight line.
+pub fn solve(map: &Map, min_moves: u32, max_moves: u32) -> u32 {
+    best_path(map, min_moves, max_moves)
+        .iter()
+        .skip(1)
+        .map(|p| map[p.pos])
+        .sum()
+}
+
+/// Crucible states along the path with the least heat loss, from the top
+/// left block to the bottom right one.
+pub fn best_path(map: &Map, min_moves: u32, max_moves: u32) -> Vec<Point> {
+    let start = Point::new(Pos::new(0, 0), Direction::Right, max_moves);
+    let mut queue = BinaryHeap::new();
+    queue.push(Node::new(start, 0));
+
+    let mut g_scores: HashMap<Point, u32> = HashMap::new();
+    g_scores.insert(start, 0);
+
+    let mut f_scores: HashMap<Point, u32> = HashMap::new();
+    f_scores.insert(start, 0);
+
+    let mut came_from: HashMap<Point, Point> = HashMap::new();
+
+    let mut path: Vec<Point> = Vec::new();
+
+    let mut seen: HashSet<Point> = HashSet::new();
+
+    while !queue.is_empty() {
+        if let Some(n) = queue.pop() {
+            let p = n.point;
+
+            if p.pos == Pos::new(map.rows() - 1, map.cols() - 1) {
+                path = reconstruct_path(&came_from, &p);
+                break;
+            }
+
+            for np in get_next(map, &p, min_moves, max_moves).iter() {
+                let new_gs = g_scores.get(&p).unwrap() + map[np.pos];
+                let old_gs = *g_scores.get(np).unwrap_or(&u32::MAX);
+
+                if new_gs < old_gs {
+                    came_from.insert(*np, p);
+
+                    g_scores.insert(*np, new_gs);
+                    let fs = new_gs + h(map, np);
+                    f_scores.insert(*np, fs);
+
+                    if !seen.contains(np) {
+                        seen.insert(*np);
+                        queue.push(Node::new(*np, fs))
+                    }
+                }
+            }
+        }
+    }
+
+    path
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Node {
+    point: Point,
+    f_score: u32,
+}
+
+impl Node {
+    fn new(point: Point, f_score: u32) -> Self {
+        Node { point, f_score }
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .f_score
+            .cmp(&self.f_score)
+            .then_with(|| self.point.cmp(&other.point))
+    }
+}
+
+fn reconstruct_path(came_from: &HashMap<Point, Point>, start: &Point) -> Vec<Point> {
+    let mut path = vec![*start];
+    let mut current = *start;
+
+    while let Some(p) = came_from.get(&current) {
+        current = *p;
+        path.push(*p)
+    }
+
+    path.reverse();
+
+    path
+}
+
+fn h(map: &Map, p: &Point) -> u32 {
+    (map.rows() - p.pos.row) as u32 + (map.cols() - p.pos.col) as u32
+}
+
+fn get_next(map: &Map, point: &Point, min_moves: u32, max_moves: u32) -> Vec<Point> {
+    let mut res = Vec::new();
+
+    if let Some(p) = continue_straight(map, point) {
+        res.push(p)
+    }
+    if let Some(p) = turn_left(map, point, min_moves, max_moves) {
+        res.push(p)
+    }
+    if let Some(p) = turn_right(map, point, min_moves, max_moves) {
+        res.push(p)
+    }
+
+    res
+}
+
+fn continue_straight(map: &Map, point: &Point) -> Option<Point> {
+    if point.moves_left == 0 {
+        return None;
+    }
+
+    let pos = map.step(point.pos, point.direction)?;
+
+    Some(Point::new(pos, point.direction, point.moves_left - 1))
+}
+
+fn turn_left(map: &Map, point: &Point, min_moves: u32, max_moves: u32) -> Option<Point> {
+    if (max_moves - point.moves_left) < min_moves {
+        return None;
+    }
+
+    let direction = point.direction.turn_left();
+    let pos = map.step(point.pos, direction)?;
+
+    Some(Point::new(pos, direction, max_moves - 1))
+}
+
+fn turn_right(map: &Map, point: &Point, min_moves: u32, max_moves: u32) -> Option<Point> {
+    if (max_moves - point.moves_left) < min_moves {
+        return None;
+    }
+
+    let direction = point.direction.turn_right();
+    let pos = map.step(point.pos, direction)?;
+
+    Some(Point::new(pos, direction, max_moves - 1))
+}
+
+/// State of the crucible: where it is, where it heads and how many blocks it
+/// may still move straight.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Hash, Ord)]
+pub struct Point {
+    pub pos: Pos,
+    pub direction: Direction,
+    pub moves_left: u32,
+}
+
+impl Point {
+    pub fn new(pos: Pos, direction: Direction, moves_left: u32) -> Self {
+        Point {
+            pos,
+            direction,
+            moves_left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day17::solve1(EXAMPLE), Ok(102));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let map = Day17::parse(EXAMPLE).unwrap();
+        let canvas = Day17::visualize(&map).unwrap();
+
+        let heat_loss: u32 = map
+            .iter()
+            .filter(|&(pos, _)| canvas.style(pos.into()) == Some(Style::Path))
+            .map(|(_, heat)| heat)
+            .sum();
+        assert_eq!(heat_loss, 102);
+        assert_eq!(canvas.style((0, 0)), Some(Style::Highlight));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day17::solve2(EXAMPLE), Ok(94));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day17>(30);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day17::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::grid("123456789", 10)) {
+            prop_assert_eq!(Day17::parse(&s).unwrap().to_string(), s);
+        }
+    }
+}
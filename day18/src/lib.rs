@@ -0,0 +1,582 @@
+use common::{
+    check::Check,
+    error::{Context, Error, Result},
+    generate::{self, Rng},
+    render::{Canvas, Style},
+    solution::Solution,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Dig plan, read as written and with instructions hidden in the colors.
+pub struct Input {
+    pub plan: Vec<Instruction>,
+    pub color_plan: Vec<Instruction>,
+}
+
+/// Day 18: Lavaduct Lagoon.
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        let mut plan = Vec::new();
+        let mut color_plan = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            plan.push(parse1(line).at_line(i, line)?);
+            color_plan.push(parse2(line).at_line(i, line)?);
+        }
+
+        if plan.is_empty() {
+            return Err(Error::new("empty input"));
+        }
+
+        Ok(Input { plan, color_plan })
+    }
+
+    /// Outline of a random shape in a grid of `size` x `size` cells, with
+    /// meters between grid lines of up to 10 in the plan and up to 1000 in
+    /// the colors.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let size = size.max(1);
+        let shape = generate::polyomino(rng, size, size);
+        let gaps = |rng: &mut Rng, max: usize| -> Vec<usize> {
+            (0..size).map(|_| rng.usize(1..max + 1)).collect()
+        };
+        let (rows, cols) = (gaps(rng, 10), gaps(rng, 10));
+        let (color_rows, color_cols) = (gaps(rng, 1000), gaps(rng, 1000));
+
+        let mut res = String::new();
+        for (direction, from, to) in outline(&shape) {
+            // Meters between grid lines `from` and `to`.
+            let steps = |rows: &[usize], cols: &[usize]| -> usize {
+                let gaps = match direction {
+                    Direction::Up | Direction::Down => rows,
+                    Direction::Left | Direction::Right => cols,
+                };
+                gaps[from.min(to)..from.max(to)].iter().sum()
+            };
+            let (letter, digit) = match direction {
+                Direction::Right => ('R', 0),
+                Direction::Down => ('D', 1),
+                Direction::Left => ('L', 2),
+                Direction::Up => ('U', 3),
+            };
+            res.push_str(&format!(
+                "{} {} (#{:05x}{})\n",
+                letter,
+                steps(&rows, &cols),
+                steps(&color_rows, &color_cols),
+                digit
+            ));
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        calc1(&input.plan)
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        calc2(&input.color_plan)
+    }
+
+    /// Flood fill against the interval scan on the plan of part 1, the one
+    /// of part 2 is too large to flood fill.
+    fn cross_check(input: &Input) -> Result<Vec<Check>> {
+        Ok(vec![Check::new(
+            1,
+            "flood fill vs intervals",
+            calc1(&input.plan)?,
+            calc2(&input.plan)?,
+        )])
+    }
+
+    /// Lagoon of part 1 with the trench and its dug out interior.
+    fn visualize(input: &Input) -> Result<Canvas> {
+        let border = trench(&input.plan);
+        let inside = interior(&input.plan);
+
+        // Plan starts at (0, 0) but the trench goes in all directions.
+        let min_row = border.iter().map(|p| p.0).min().unwrap_or(0);
+        let min_col = border.iter().map(|p| p.1).min().unwrap_or(0);
+        let max_row = border.iter().map(|p| p.0).max().unwrap_or(0);
+        let max_col = border.iter().map(|p| p.1).max().unwrap_or(0);
+        let shift =
+            |(row, col): (isize, isize)| ((row - min_row) as usize, (col - min_col) as usize);
+
+        let mut canvas = Canvas::new(
+            (max_row - min_row + 1) as usize,
+            (max_col - min_col + 1) as usize,
+            '.',
+        );
+        for &p in &border {
+            canvas.draw(shift(p), '#', Style::Path);
+        }
+        for &p in &inside {
+            canvas.draw(shift(p), '#', Style::Visited);
+        }
+        canvas.mark(shift((0, 0)), Style::Highlight);
+
+        Ok(canvas)
+    }
+}
+
+/// Cubic meters of lava the lagoon holds, by summing up row intervals inside
+/// the trench, works with huge plans.
+pub fn calc2(instructions: &[Instruction]) -> Result<u64> {
+    let mut cur: (isize, isize) = (0, 0);
+    let mut border: BTreeMap<isize, BTreeSet<(isize, isize)>> = BTreeMap::new();
+    let mut dir_in: BTreeMap<(isize, isize, isize), Direction> = BTreeMap::new();
+    let mut dir_out: BTreeMap<(isize, isize, isize), Direction> = BTreeMap::new();
+    let mut prev_dir: Direction = instructions.last().unwrap().direction;
+    let mut last_entry: Option<(isize, isize, isize)> = None;
+    let mut min_row = 0;
+    let mut max_row = 0;
+    let mut min_col = 0;
+    let mut max_col = 0;
+
+    for inst in instructions {
+        if let Some(v) = last_entry {
+            dir_out.insert(v, inst.direction);
+            last_entry = None;
+        }
+
+        match inst.direction {
+            Direction::Up => {
+                for i in 0..inst.steps {
+                    if i != inst.steps - 1 {
+                        insert(&mut border, cur.0 - 1, cur.1, cur.1);
+                    }
+                    cur.0 -= 1;
+                }
+            }
+            Direction::Down => {
+                for i in 0..inst.steps {
+                    if i != inst.steps - 1 {
+                        insert(&mut border, cur.0 + 1, cur.1, cur.1);
+                    }
+                    cur.0 += 1;
+                }
+            }
+            Direction::Left => {
+                let v = (cur.0, cur.1 - inst.steps, cur.1);
+                insert(&mut border, cur.0, cur.1 - inst.steps, cur.1);
+                dir_in.insert(v, prev_dir);
+                last_entry = Some(v);
+                cur.1 -= inst.steps;
+            }
+            Direction::Right => {
+                let v = (cur.0, cur.1, cur.1 + inst.steps);
+                insert(&mut border, cur.0, cur.1, cur.1 + inst.steps);
+                dir_in.insert(v, prev_dir);
+                last_entry = Some(v);
+                cur.1 += inst.steps;
+            }
+        }
+
+        if cur.0 < min_row {
+            min_row = cur.0;
+        }
+        if cur.0 > max_row {
+            max_row = cur.0;
+        }
+        if cur.1 < min_col {
+            min_col = cur.1;
+        }
+        if cur.1 > max_col {
+            max_col = cur.1;
+        }
+
+        prev_dir = inst.direction;
+    }
+
+    let mut res: u64 = 0;
+
+    for (row, set) in &border {
+        let mut last_end = 0;
+        let mut crossings = 0;
+        for (start, end) in set {
+            let len = (end - start) as u64 + 1;
+            res += len;
+
+            if crossings % 2 == 1 {
+                res += (start - last_end) as u64 - 1;
+            }
+
+            if len > 1 {
+                let d1 = dir_in.get(&(*row, *start, *end)).unwrap();
+                let d2 = dir_out.get(&(*row, *start, *end)).unwrap();
+
+                if d1 == d2 {
+                    crossings += 1;
+                }
+            } else {
+                crossings += 1;
+            }
+            last_end = *end;
+        }
+    }
+
+    Ok(res)
+}
+
+fn insert(
+    border: &mut BTreeMap<isize, BTreeSet<(isize, isize)>>,
+    row: isize,
+    start: isize,
+    end: isize,
+) {
+    border
+        .entry(row)
+        .and_modify(|e| {
+            e.insert((start, end));
+        })
+        .or_insert(vec![(start, end)].into_iter().collect());
+}
+
+/// Cubic meters of lava the lagoon holds, by flood filling the dug trench.
+pub fn calc1(instructions: &[Instruction]) -> Result<u64> {
+    let border = trench(instructions);
+
+    Ok((border.len() + interior(instructions).len()) as u64)
+}
+
+/// Positions of the trench dug by following the plan from (0, 0), as (row,
+/// column).
+pub fn trench(instructions: &[Instruction]) -> HashSet<(isize, isize)> {
+    let mut cur = (0, 0);
+    let mut border: HashSet<(isize, isize)> = HashSet::new();
+    border.insert(cur);
+
+    for inst in instructions {
+        let (dr, dc) = inst.direction.delta();
+        for _ in 0..inst.steps {
+            cur = (cur.0 + dr, cur.1 + dc);
+            border.insert(cur);
+        }
+    }
+
+    border
+}
+
+/// Positions enclosed by the trench: those which can't be reached from
+/// outside of it without crossing it. The flood fill runs on a grid of double
+/// resolution where the trench only joins positions dug one after another,
+/// so it passes between trenches which run side by side.
+pub fn interior(instructions: &[Instruction]) -> HashSet<(isize, isize)> {
+    let mut cur = (0, 0);
+    let mut wall: HashSet<(isize, isize)> = HashSet::new();
+    wall.insert(cur);
+
+    for inst in instructions {
+        let (dr, dc) = inst.direction.delta();
+        for _ in 0..inst.steps {
+            wall.insert((2 * cur.0 + dr, 2 * cur.1 + dc));
+            cur = (cur.0 + dr, cur.1 + dc);
+            wall.insert((2 * cur.0, 2 * cur.1));
+        }
+    }
+
+    // Bounds with a margin, so the outside surrounds the trench.
+    let min_row = wall.iter().map(|p| p.0).min().unwrap() - 2;
+    let min_col = wall.iter().map(|p| p.1).min().unwrap() - 2;
+    let max_row = wall.iter().map(|p| p.0).max().unwrap() + 2;
+    let max_col = wall.iter().map(|p| p.1).max().unwrap() + 2;
+
+    let mut outside = HashSet::new();
+    let mut stack = vec![(min_row, min_col)];
+    while let Some((row, col)) = stack.pop() {
+        if row < min_row
+            || row > max_row
+            || col < min_col
+            || col > max_col
+            || wall.contains(&(row, col))
+            || !outside.insert((row, col))
+        {
+            continue;
+        }
+        stack.extend([
+            (row - 1, col),
+            (row + 1, col),
+            (row, col - 1),
+            (row, col + 1),
+        ]);
+    }
+
+    // Positions of the plan are at even rows and columns.
+    (min_row / 2..=max_row / 2)
+        .flat_map(|row| (min_col / 2..=max_col / 2).map(move |col| (row, col)))
+        .filter(|&(row, col)| {
+            let p = (2 * row, 2 * col);
+            !wall.contains(&p) && !outside.contains(&p)
+        })
+        .collect()
+}
+
+/// Outline of a shape of cells without holes and cells touching only by
+/// corners, as runs in a direction from grid line `from` to `to`. Starts
+/// going right from the top left corner of the first cell.
+fn outline(shape: &[Vec<bool>]) -> Vec<(Direction, usize, usize)> {
+    let cell = |row: isize, col: isize| {
+        row >= 0
+            && col >= 0
+            && shape.get(row as usize).and_then(|r| r.get(col as usize)) == Some(&true)
+    };
+    // Whether the side of cells from the corner at `row`, `col` in
+    // `direction` separates the shape from the outside.
+    let side = |row: isize, col: isize, direction: Direction| match direction {
+        Direction::Up => cell(row - 1, col - 1) != cell(row - 1, col),
+        Direction::Down => cell(row, col - 1) != cell(row, col),
+        Direction::Left => cell(row - 1, col - 1) != cell(row, col - 1),
+        Direction::Right => cell(row - 1, col) != cell(row, col),
+    };
+
+    let Some(start) = shape.iter().enumerate().find_map(|(row, cells)| {
+        let col = cells.iter().position(|&c| c)?;
+        Some((row as isize, col as isize))
+    }) else {
+        return Vec::new();
+    };
+
+    let mut res: Vec<(Direction, usize, usize)> = Vec::new();
+    let (mut row, mut col) = start;
+    let mut direction = Direction::Right;
+    loop {
+        let (from, to) = match direction {
+            Direction::Up => (row, row - 1),
+            Direction::Down => (row, row + 1),
+            Direction::Left => (col, col - 1),
+            Direction::Right => (col, col + 1),
+        };
+        match direction {
+            Direction::Up | Direction::Down => row = to,
+            Direction::Left | Direction::Right => col = to,
+        }
+        match res.last_mut() {
+            Some(last) if last.0 == direction => last.2 = to as usize,
+            _ => res.push((direction, from as usize, to as usize)),
+        }
+
+        if (row, col) == start {
+            return res;
+        }
+        let back = match direction {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        };
+        direction = [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .find(|&d| d != back && side(row, col, d))
+        .expect("outline is a loop");
+    }
+}
+
+/// Direction to dig in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Change of row and column of a step.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+}
+
+/// Dig `steps` meters in `direction`.
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub steps: isize,
+}
+
+/// Instruction as written in the plan.
+pub fn parse1(s: &str) -> Result<Instruction> {
+    let mut parts = s.split_whitespace();
+
+    let direction_str = parts
+        .next()
+        .ok_or_else(|| Error::new("direction is missing"))?;
+
+    let direction: Direction = match direction_str {
+        "U" => Direction::Up,
+        "D" => Direction::Down,
+        "L" => Direction::Left,
+        "R" => Direction::Right,
+        _ => return Err(Error::invalid("direction", direction_str)),
+    };
+
+    let steps_str = parts
+        .next()
+        .ok_or_else(|| Error::new("steps are missing"))?;
+    let steps: isize = steps_str
+        .parse()
+        .map_err(|_| Error::invalid("steps", steps_str))?;
+
+    Ok(Instruction { direction, steps })
+}
+
+/// Instruction hidden in the color of the plan line.
+pub fn parse2(s: &str) -> Result<Instruction> {
+    let mut parts = s.split_whitespace();
+    parts.next();
+    parts.next();
+
+    let color = parts.next().ok_or_else(|| Error::new("color is missing"))?;
+
+    let hex = color
+        .strip_prefix("(#")
+        .and_then(|c| c.strip_suffix(')'))
+        .filter(|hex| hex.len() == 6 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+        .ok_or_else(|| Error::invalid("color", color))?;
+    let steps =
+        isize::from_str_radix(&hex[0..5], 16).map_err(|_| Error::invalid("color", color))?;
+    let direction = match &hex[5..] {
+        "0" => Direction::Right,
+        "1" => Direction::Down,
+        "2" => Direction::Left,
+        "3" => Direction::Up,
+        _ => return Err(Error::invalid("color", color)),
+    };
+
+    Ok(Instruction { direction, steps })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{render::Format, testing};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day18::solve1(EXAMPLE), Ok(62));
+    }
+
+    #[test]
+    fn test_part1_adjacent_trenches() {
+        // Trenches going down and up right next to each other.
+        let plan = "R 2 (#000000)\nD 2 (#000000)\nR 1 (#000000)\nU 2 (#000000)\n\
+                    R 2 (#000000)\nD 4 (#000000)\nL 5 (#000000)\nU 4 (#000000)\n";
+        assert_eq!(Day18::solve1(plan), Ok(30));
+    }
+
+    #[test]
+    fn test_part1_side_by_side() {
+        // Room in the middle is outside, it opens to the bottom between the
+        // trenches on both sides of its exit, which are next to each other.
+        let plan: Vec<String> = [
+            "R 6", "D 6", "L 3", "U 2", "R 2", "U 3", "L 4", "D 3", "R 1", "D 2", "L 2", "U 6",
+        ]
+        .iter()
+        .map(|step| format!("{} (#000000)", step))
+        .collect();
+
+        assert_eq!(Day18::solve1(&testing::lines(&plan)), Ok(43));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        let canvas = Day18::visualize(&input).unwrap();
+
+        assert_eq!(
+            canvas.render(Format::Ascii),
+            testing::lines(&[
+                "#######", "#######", "#######", "..#####", "..#####", "#######", "#####..",
+                "#######", ".######", ".######",
+            ])
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day18::solve2(EXAMPLE), Ok(952408144115));
+    }
+
+    #[test]
+    fn test_outline() {
+        let shape = vec![vec![true, true], vec![true, false]];
+
+        assert_eq!(
+            outline(&shape),
+            vec![
+                (Direction::Right, 0, 2),
+                (Direction::Down, 0, 1),
+                (Direction::Left, 2, 1),
+                (Direction::Down, 1, 2),
+                (Direction::Left, 1, 0),
+                (Direction::Up, 2, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day18>(50);
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let checks = Day18::cross_check(&Day18::parse(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(
+            checks,
+            vec![Check::new(1, "flood fill vs intervals", 62, 62)]
+        );
+
+        testing::cross_check::<Day18>(50);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day18::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            plan in prop::collection::vec(("[UDLR]", 1..100_isize, 0..0x100000_isize, 0..4_u8), 1..10)
+        ) {
+            let lines: Vec<String> = plan
+                .iter()
+                .map(|(dir, steps, color_steps, color_dir)| format!("{} {} (#{:05x}{})", dir, steps, color_steps, color_dir))
+                .collect();
+
+            let input = Day18::parse(&testing::lines(&lines)).unwrap();
+
+            let directions = [Direction::Right, Direction::Down, Direction::Left, Direction::Up];
+            for (i, (dir, steps, color_steps, color_dir)) in plan.iter().enumerate() {
+                let direction = directions["RDLU".find(dir.as_str()).unwrap()];
+                prop_assert_eq!(input.plan[i].direction, direction);
+                prop_assert_eq!(input.plan[i].steps, *steps);
+
+                prop_assert_eq!(input.color_plan[i].direction, directions[*color_dir as usize]);
+                prop_assert_eq!(input.color_plan[i].steps, *color_steps);
+            }
+        }
+    }
+}
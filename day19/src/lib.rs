@@ -0,0 +1,716 @@
+use common::{
+    error::{self, Context, Error, Result},
+    generate::Rng,
+    graph::Graph,
+    solution::Solution,
+};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// Workflows and ratings of parts.
+pub struct Input(pub Data);
+
+/// Day 19: Aplenty.
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(read_data(input)?))
+    }
+
+    /// Tree of `size` workflows starting at "in" and `size` parts.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // Three letters, so no name is "in".
+        let name = |i: usize| -> String {
+            if i == 0 {
+                return "in".to_string();
+            }
+            [i / 676 % 26, i / 26 % 26, i % 26]
+                .iter()
+                .map(|&l| (b'a' + l as u8) as char)
+                .collect()
+        };
+        let count = size.clamp(1, 26 * 26 * 26);
+
+        let mut children = vec![Vec::new(); count];
+        for i in 1..count {
+            children[rng.usize(0..i)].push(name(i));
+        }
+
+        let mut res = String::new();
+        for (i, children) in children.into_iter().enumerate() {
+            let mut targets = children;
+            while targets.len() < 2 {
+                targets.push(rng.pick(&["A", "R"]).to_string());
+            }
+            rng.shuffle(&mut targets);
+
+            let default = targets.pop().unwrap();
+            let rules: Vec<String> = targets
+                .iter()
+                .map(|target| {
+                    format!(
+                        "{}{}{}:{}",
+                        rng.pick(&['x', 'm', 'a', 's']),
+                        rng.pick(&['<', '>']),
+                        rng.usize(2..4000),
+                        target
+                    )
+                })
+                .collect();
+            res.push_str(&format!("{}{{{},{}}}\n", name(i), rules.join(","), default));
+        }
+
+        res.push('\n');
+        for _ in 0..size.max(1) {
+            let [x, m, a, s] = [(); 4].map(|_| rng.usize(1..4001));
+            res.push_str(&format!("{{x={},m={},a={},s={}}}\n", x, m, a, s));
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        part1::solve(&input.0)
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        Ok(part2::solve(&input.0))
+    }
+
+    fn graph(input: &Input) -> Result<Graph> {
+        Ok(graph(&input.0))
+    }
+}
+
+/// Workflows with an edge to each target of their rules labelled with the
+/// condition of the rule, "in" is a box and the final "A" and "R" are double
+/// circles.
+pub fn graph(data: &Data) -> Graph {
+    let mut names: Vec<&String> = data.workflows.keys().collect();
+    names.sort();
+
+    let mut res = Graph::directed("workflows");
+    res.node("in", &[("shape", "box")]);
+    res.node("A", &[("shape", "doublecircle")]);
+    res.node("R", &[("shape", "doublecircle")]);
+    for name in names {
+        for rule in &data.workflows[name].rules {
+            match rule {
+                Rule::Conditional(condition, action) => {
+                    let label = condition.to_string();
+                    res.edge(name, &action.to_string(), &[("label", &label)]);
+                }
+                Rule::Unconditional(action) => res.edge(name, &action.to_string(), &[]),
+            }
+        }
+    }
+    res
+}
+
+/// Counting accepted combinations of ratings by splitting ranges on rules.
+pub mod part2 {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone)]
+    struct State {
+        x: (u64, u64),
+        m: (u64, u64),
+        a: (u64, u64),
+        s: (u64, u64),
+    }
+
+    impl State {
+        fn apply_condition(&self, condition: &Condition) -> Option<State> {
+            let x = self.x;
+            let m = self.m;
+            let a = self.a;
+            let s = self.s;
+
+            match condition.category {
+                Category::X => Some(State {
+                    x: apply_condition(condition, x)?,
+                    m,
+                    a,
+                    s,
+                }),
+                Category::M => Some(State {
+                    x,
+                    m: apply_condition(condition, m)?,
+                    a,
+                    s,
+                }),
+                Category::A => Some(State {
+                    x,
+                    m,
+                    a: apply_condition(condition, a)?,
+                    s,
+                }),
+                Category::S => Some(State {
+                    x,
+                    m,
+                    a,
+                    s: apply_condition(condition, s)?,
+                }),
+            }
+        }
+
+        fn count(&self) -> u64 {
+            (self.x.1 - self.x.0 + 1)
+                * (self.m.1 - self.m.0 + 1)
+                * (self.a.1 - self.a.0 + 1)
+                * (self.s.1 - self.s.0 + 1)
+        }
+    }
+
+    fn apply_condition(condition: &Condition, range: (u64, u64)) -> Option<(u64, u64)> {
+        match condition.sign {
+            Sign::Gt => {
+                if condition.value >= range.1 {
+                    None
+                } else if condition.value > range.0 && condition.value < range.1 {
+                    Some((condition.value + 1, range.1))
+                } else {
+                    Some(range)
+                }
+            }
+            Sign::Lt => {
+                if condition.value <= range.0 {
+                    None
+                } else if condition.value > range.0 && condition.value < range.1 {
+                    Some((range.0, condition.value - 1))
+                } else {
+                    Some(range)
+                }
+            }
+        }
+    }
+
+    fn neg(condition: &Condition) -> Condition {
+        Condition {
+            category: condition.category,
+            sign: match condition.sign {
+                Sign::Gt => Sign::Lt,
+                Sign::Lt => Sign::Gt,
+            },
+            value: match condition.sign {
+                Sign::Gt => condition.value + 1,
+                Sign::Lt => condition.value - 1,
+            },
+        }
+    }
+
+    pub fn solve(data: &Data) -> u64 {
+        let mut res = 0;
+        let start = State {
+            x: (1, 4000),
+            m: (1, 4000),
+            a: (1, 4000),
+            s: (1, 4000),
+        };
+
+        let states = process(start, "in", 0, data);
+
+        for state in &states {
+            res += state.count();
+        }
+
+        res
+    }
+
+    fn process(state: State, workflow_name: &str, rule_index: usize, data: &Data) -> Vec<State> {
+        let mut res = Vec::new();
+
+        if let Some(workflow) = data.workflows.get(workflow_name) {
+            let mut current = state;
+            for ri in rule_index..workflow.rules.len() {
+                let rule = &workflow.rules[ri];
+                match rule {
+                    Rule::Unconditional(action) => match action {
+                        Action::Accept => res.push(current),
+                        Action::Reject => {}
+                        Action::Goto(name) => res.extend(process(current, name, 0, data)),
+                    },
+                    Rule::Conditional(condition, action) => {
+                        if let Some(s) = current.apply_condition(condition) {
+                            match action {
+                                Action::Accept => res.push(s),
+                                Action::Reject => {}
+                                Action::Goto(name) => res.extend(process(s, name, 0, data)),
+                            }
+                        }
+                        if let Some(next) = current.apply_condition(&neg(condition)) {
+                            current = next;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        res
+    }
+}
+
+/// Sorting the listed parts through workflows.
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(data: &Data) -> Result<u64> {
+        let mut res = 0;
+        let start = data
+            .workflows
+            .get("in")
+            .ok_or_else(|| Error::new("no workflow \"in\""))?;
+
+        'outer: for rating in &data.ratings {
+            let mut workflow_ref = start;
+
+            while let Some(action) = workflow_ref.apply(rating) {
+                match action {
+                    Action::Accept => {
+                        res += rating.values.values().sum::<u64>();
+                        continue 'outer;
+                    }
+                    Action::Reject => continue 'outer,
+                    Action::Goto(name) => {
+                        workflow_ref = data
+                            .workflows
+                            .get(&name)
+                            .ok_or_else(|| Error::new(format!("unknown workflow {}", name)))?;
+                    }
+                }
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+fn read_data(input: &str) -> Result<Data> {
+    let mut workflows = HashMap::new();
+    let mut ratings = Vec::new();
+    let mut parsing_workflows = true;
+
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            parsing_workflows = false;
+            continue;
+        }
+
+        if parsing_workflows {
+            let workflow: Workflow = line.parse().at_line(i, line)?;
+            workflows.insert(workflow.name.clone(), workflow);
+        } else {
+            ratings.push(line.parse().at_line(i, line)?);
+        }
+    }
+
+    Ok(Data { workflows, ratings })
+}
+
+/// Workflows by name and ratings of parts.
+#[derive(Debug)]
+pub struct Data {
+    pub workflows: HashMap<String, Workflow>,
+    pub ratings: Vec<Rating>,
+}
+
+/// Ratings of a part in each category.
+#[derive(Debug, Clone)]
+pub struct Rating {
+    pub values: HashMap<Category, u64>,
+}
+
+impl FromStr for Rating {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut values = HashMap::new();
+        let parsed: Vec<(Category, u64)> = s
+            .trim_start_matches("{")
+            .trim_end_matches("}")
+            .split(",")
+            .map(|p| {
+                let mut parts = p.splitn(2, "=");
+                let category: Category = parts.next().unwrap_or_default().parse()?;
+                let value: u64 = error::parse(
+                    parts
+                        .next()
+                        .ok_or_else(|| Error::new("missing value").with_text(p))?,
+                    "value",
+                )?;
+
+                Ok((category, value))
+            })
+            .collect::<Result<Vec<(Category, u64)>>>()?;
+
+        for (c, v) in &parsed {
+            values.insert(*c, *v);
+        }
+
+        Ok(Rating { values })
+    }
+}
+
+/// Named list of rules, the first matching one decides.
+#[derive(Debug)]
+pub struct Workflow {
+    pub name: String,
+    pub rules: Vec<Rule>,
+}
+
+impl FromStr for Workflow {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.splitn(2, '{');
+        let name = parts.next().unwrap_or_default().to_string();
+
+        let rules_part = parts
+            .next()
+            .ok_or_else(|| Error::new("missing rules"))?
+            .trim_end_matches('}');
+
+        let rules: Vec<Rule> = rules_part
+            .split(',')
+            .map(|s| s.parse::<Rule>())
+            .collect::<Result<Vec<Rule>>>()?;
+
+        Ok(Workflow { name, rules })
+    }
+}
+
+impl Workflow {
+    /// Action of the first rule which matches the rating.
+    pub fn apply(&self, rating: &Rating) -> Option<Action> {
+        for rule in &self.rules {
+            match rule {
+                Rule::Unconditional(action) => {
+                    return Some(action.clone());
+                }
+                Rule::Conditional(condition, action) => {
+                    if condition.check(&rating.values) {
+                        return Some(action.clone());
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Rule of a workflow, applies an action if its condition matches.
+#[derive(Debug)]
+pub enum Rule {
+    Conditional(Condition, Action),
+    Unconditional(Action),
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some((cond, act)) = s.split_once(':') {
+            Ok(Rule::Conditional(cond.parse()?, act.parse()?))
+        } else {
+            Ok(Rule::Unconditional(s.parse()?))
+        }
+    }
+}
+
+/// Comparison of a category rating with a value.
+#[derive(Debug, Copy, Clone)]
+pub struct Condition {
+    pub category: Category,
+    pub sign: Sign,
+    pub value: u64,
+}
+
+impl FromStr for Condition {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() < 3 {
+            return Err(Error::invalid("condition", s));
+        }
+
+        // Category and sign are single ASCII characters, `get` fails on
+        // anything else instead of slicing in the middle of a character.
+        let invalid = || Error::invalid("condition", s);
+        let category: Category = s.get(0..1).ok_or_else(invalid)?.parse()?;
+        let sign: Sign = s.get(1..2).ok_or_else(invalid)?.parse()?;
+        let value: u64 = error::parse(s.get(2..).ok_or_else(invalid)?, "value")?;
+
+        Ok(Condition {
+            category,
+            sign,
+            value,
+        })
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = match self.sign {
+            Sign::Gt => '>',
+            Sign::Lt => '<',
+        };
+        write!(f, "{}{}{}", self.category, sign, self.value)
+    }
+}
+
+impl Condition {
+    /// Whether the ratings satisfy the condition.
+    pub fn check(&self, values: &HashMap<Category, u64>) -> bool {
+        let value = values.get(&self.category).unwrap();
+        match self.sign {
+            Sign::Gt => value > &self.value,
+            Sign::Lt => value < &self.value,
+        }
+    }
+}
+
+/// Rating category: x, m, a or s.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl FromStr for Category {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "x" => Ok(Category::X),
+            "m" => Ok(Category::M),
+            "a" => Ok(Category::A),
+            "s" => Ok(Category::S),
+            _ => Err(Error::invalid("category", s)),
+        }
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Category::X => 'x',
+            Category::M => 'm',
+            Category::A => 'a',
+            Category::S => 's',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// Comparison operator of a condition.
+#[derive(Debug, Clone, Copy)]
+pub enum Sign {
+    Gt,
+    Lt,
+}
+
+impl FromStr for Sign {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "<" => Ok(Sign::Lt),
+            ">" => Ok(Sign::Gt),
+            _ => Err(Error::invalid("sign", s)),
+        }
+    }
+}
+
+/// Outcome of a rule: accept, reject or send to another workflow.
+#[derive(Debug, Clone)]
+pub enum Action {
+    Accept,
+    Reject,
+    Goto(String),
+}
+
+impl FromStr for Action {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "A" => Ok(Action::Accept),
+            "R" => Ok(Action::Reject),
+            name => Ok(Action::Goto(name.to_string())),
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Accept => write!(f, "A"),
+            Action::Reject => write!(f, "R"),
+            Action::Goto(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_parse_rule() {
+        assert!(matches!(
+            "a<2006:qkq".parse(),
+            Ok(Rule::Conditional(
+                Condition {
+                    category: Category::A,
+                    sign: Sign::Lt,
+                    value: 2006
+                },
+                Action::Goto(name)
+            )) if name == "qkq"
+        ));
+        assert!(matches!(
+            "A".parse(),
+            Ok(Rule::Unconditional(Action::Accept))
+        ));
+        assert!("y>10:R".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_parse_workflow() {
+        let workflow: Workflow = "px{a<2006:qkq,m>2090:A,rfg}".parse().unwrap();
+
+        assert_eq!(workflow.name, "px");
+        assert_eq!(workflow.rules.len(), 3);
+        assert!(matches!(
+            workflow.rules[2],
+            Rule::Unconditional(Action::Goto(ref name)) if name == "rfg"
+        ));
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day19::solve1(EXAMPLE), Ok(19114));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day19::solve2(EXAMPLE), Ok(167409079868000));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day19>(500);
+    }
+
+    #[test]
+    fn test_graph() {
+        let graph = Day19::graph(&Day19::parse(EXAMPLE).unwrap()).unwrap();
+
+        // 11 workflows with A and R, one edge per rule.
+        assert_eq!(graph.node_count(), 13);
+        assert_eq!(graph.edge_count(), 25);
+        let dot = graph.to_dot();
+        assert!(dot.contains("  \"px\" -> \"qkq\" [label=\"a<2006\"];\n"));
+        assert!(dot.contains("  \"px\" -> \"rfg\";\n"));
+        assert!(graph
+            .to_graphml()
+            .contains("<data key=\"e_label\">a&lt;2006</data>"));
+    }
+
+    /// Workflow in the format of the puzzle input.
+    fn render(workflow: &Workflow) -> String {
+        let action = |a: &Action| match a {
+            Action::Accept => "A".to_string(),
+            Action::Reject => "R".to_string(),
+            Action::Goto(name) => name.clone(),
+        };
+
+        let rules: Vec<String> = workflow
+            .rules
+            .iter()
+            .map(|rule| match rule {
+                Rule::Conditional(c, a) => {
+                    let sign = match c.sign {
+                        Sign::Lt => '<',
+                        Sign::Gt => '>',
+                    };
+                    format!("{}{}{}:{}", category(c.category), sign, c.value, action(a))
+                }
+                Rule::Unconditional(a) => action(a),
+            })
+            .collect();
+
+        format!("{}{{{}}}", workflow.name, rules.join(","))
+    }
+
+    fn category(c: Category) -> char {
+        match c {
+            Category::X => 'x',
+            Category::M => 'm',
+            Category::A => 'a',
+            Category::S => 's',
+        }
+    }
+
+    fn target() -> impl Strategy<Value = String> {
+        prop_oneof![Just("A".to_string()), Just("R".to_string()), "[a-z]{2,3}"]
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day19::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            workflows in prop::collection::btree_map(
+                "[a-z]{2,3}",
+                (prop::collection::vec(("[xmas]", "[<>]", 1..4000_u64, target()), 0..4), target()),
+                1..5,
+            ),
+            ratings in prop::collection::vec([1..4000_u64, 1..4000_u64, 1..4000_u64, 1..4000_u64], 0..5),
+        ) {
+            let workflows: Vec<String> = workflows
+                .iter()
+                .map(|(name, (rules, fallback))| {
+                    let mut rules: Vec<String> = rules
+                        .iter()
+                        .map(|(category, sign, value, target)| format!("{}{}{}:{}", category, sign, value, target))
+                        .collect();
+                    rules.push(fallback.clone());
+                    format!("{}{{{}}}", name, rules.join(","))
+                })
+                .collect();
+            let lines: Vec<String> = ratings
+                .iter()
+                .map(|[x, m, a, s]| format!("{{x={},m={},a={},s={}}}", x, m, a, s))
+                .collect();
+            let text = testing::lines(&workflows) + "\n" + &testing::lines(&lines);
+
+            let data = Day19::parse(&text).unwrap().0;
+
+            let mut parsed: Vec<String> = data.workflows.values().map(render).collect();
+            let mut expected = workflows.clone();
+            parsed.sort();
+            expected.sort();
+            prop_assert_eq!(parsed, expected);
+
+            prop_assert_eq!(data.ratings.len(), ratings.len());
+            for (rating, values) in data.ratings.iter().zip(&ratings) {
+                for (c, value) in "xmas".chars().zip(values) {
+                    prop_assert_eq!(rating.values.get(&c.to_string().parse().unwrap()), Some(value));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,655 @@
+use common::{
+    error::{self, Error, Result},
+    generate::Rng,
+    solution::Solution,
+    stream::{self, Streaming},
+};
+use std::{
+    cmp::{max, Reverse},
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    io::BufRead,
+    sync::LazyLock,
+};
+
+/// Game with the cubes shown in each of its draws.
+#[derive(Debug)]
+pub struct Game {
+    pub index: u32,
+    pub cubes: Vec<Cubes>,
+}
+
+impl Game {
+    /// Parses "Game N: <draw>; <draw>..." line.
+    pub fn parse(s: &str) -> Result<Game> {
+        let (game_info, rounds_str) = s
+            .split_once(": ")
+            .ok_or_else(|| Error::new("expected \"Game N: ...\"").with_text(s))?;
+        let game_index_str = game_info
+            .strip_prefix("Game ")
+            .ok_or_else(|| Error::invalid("game", game_info))?;
+
+        Ok(Game {
+            index: error::parse(game_index_str, "game number")?,
+            cubes: rounds_str
+                .split("; ")
+                .map(Cubes::parse)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Fewest cubes of each colour which make the game possible.
+    pub fn min_set(&self) -> Cubes {
+        let mut cubes = Cubes::default();
+
+        for draw in &self.cubes {
+            for (color, &count) in &draw.0 {
+                let min = cubes.0.entry(color.clone()).or_default();
+                *min = max(*min, count);
+            }
+        }
+
+        cubes
+    }
+}
+
+/// Counts of cubes by colour, colours which aren't listed have none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes(pub BTreeMap<String, u32>);
+
+impl Cubes {
+    /// Bag of part 1, 12 red, 13 green and 14 blue cubes.
+    pub fn bag() -> Cubes {
+        let counts = [("red", 12), ("green", 13), ("blue", 14)];
+        Cubes(
+            counts
+                .into_iter()
+                .map(|(color, count)| (color.to_string(), count))
+                .collect(),
+        )
+    }
+
+    /// Parses "<count> <colour>, ..." draw, colours can be any text.
+    pub fn parse(s: &str) -> Result<Cubes> {
+        let mut cubes = Cubes::default();
+        for part in s.split(", ") {
+            let (count_str, color) = part
+                .split_once(' ')
+                .ok_or_else(|| Error::new("expected \"<count> <color>\"").with_text(part))?;
+            let count = error::parse(count_str, "count")?;
+            if color.is_empty() {
+                return Err(Error::invalid("color", part));
+            }
+            if cubes.0.insert(color.to_string(), count).is_some() {
+                return Err(Error::new("repeated color").with_text(color));
+            }
+        }
+        Ok(cubes)
+    }
+
+    pub fn get(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    /// Product of the counts of the colours in `bag`, the power of part 2
+    /// with the bag of part 1.
+    pub fn power(&self, bag: &Cubes) -> u64 {
+        bag.0
+            .keys()
+            .map(|color| u64::from(self.get(color)))
+            .product()
+    }
+
+    /// Whether the cubes fit in `bag`.
+    pub fn fits(&self, bag: &Cubes) -> bool {
+        self.0.iter().all(|(color, &count)| count <= bag.get(color))
+    }
+}
+
+/// "<count> <colour>, ..." in the order of colours.
+impl Display for Cubes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts: Vec<String> = self
+            .0
+            .iter()
+            .map(|(color, count)| format!("{} {}", count, color))
+            .collect();
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
+/// Games listed in the puzzle input.
+pub struct Input(pub Vec<Game>);
+
+impl Input {
+    /// Numbers of the games which are possible with `bag`.
+    pub fn feasible(&self, bag: &Cubes) -> BTreeSet<u32> {
+        self.0
+            .iter()
+            .filter(|g| g.min_set().fits(bag))
+            .map(|g| g.index)
+            .collect()
+    }
+}
+
+/// Bag of part 1.
+static BAG: LazyLock<Cubes> = LazyLock::new(Cubes::bag);
+
+/// Day 2: Cube Conundrum.
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(
+            stream::lines::<Self, _>(input.as_bytes()).collect::<Result<_>>()?,
+        ))
+    }
+
+    /// `size` games of up to 6 draws with up to 20 cubes of each colour.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut res = String::new();
+        for game in 1..=size {
+            let draws: Vec<String> = (0..rng.usize(1..7))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    rng.shuffle(&mut colors);
+                    let shown: Vec<String> = colors[..rng.usize(1..4)]
+                        .iter()
+                        .map(|color| format!("{} {}", rng.usize(1..21), color))
+                        .collect();
+                    shown.join(", ")
+                })
+                .collect();
+            res += &format!("Game {}: {}\n", game, draws.join("; "));
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        Ok(input
+            .0
+            .iter()
+            .filter(|g| g.min_set().fits(&BAG))
+            .map(|g| u64::from(g.index))
+            .sum())
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        Ok(input.0.iter().map(|g| g.min_set().power(&BAG)).sum())
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Result<(u64, u64)> {
+        stream::solve::<Self>(reader)
+    }
+}
+
+impl Day2 {
+    /// Games possible with the bag of the query, such as "12 red, 13 green,
+    /// 14 blue".
+    pub fn query(input: &Input, query: &str) -> Result<String> {
+        let bag = Cubes::parse(query).map_err(|err| err.at_line(0, query))?;
+        let games = input.feasible(&bag);
+
+        let numbers: Vec<String> = games.iter().map(|n| n.to_string()).collect();
+        Ok(format!(
+            "bag: {}\npossible games: {}\n{} of {} games possible, sum of numbers {}\n",
+            bag,
+            numbers.join(", "),
+            games.len(),
+            input.0.len(),
+            games.iter().map(|&n| u64::from(n)).sum::<u64>()
+        ))
+    }
+
+    /// Analysis of the game log with the bag of part 1, with the smallest
+    /// changes of the bag admitting up to `most` more games.
+    pub fn report(input: &Input, most: usize) -> String {
+        analysis::report(&input.0, &BAG, most)
+    }
+}
+
+impl Streaming for Day2 {
+    type Line = Game;
+    /// Sums of both parts.
+    type State = (u64, u64);
+
+    fn parse_line(line: &str) -> Result<Game> {
+        Game::parse(line)
+    }
+
+    fn push(sums: &mut (u64, u64), game: &Game) -> Result<()> {
+        let min_set = game.min_set();
+        if min_set.fits(&BAG) {
+            sums.0 += u64::from(game.index);
+        }
+        sums.1 += min_set.power(&BAG);
+        Ok(())
+    }
+
+    fn answers(sums: &(u64, u64)) -> (u64, u64) {
+        *sums
+    }
+}
+
+/// Statistics of the whole game log relative to a bag.
+pub mod analysis {
+    use super::*;
+
+    /// Smallest bag with which every game is possible.
+    pub fn min_bag(games: &[Game]) -> Cubes {
+        let mut bag = Cubes::default();
+        for game in games {
+            for (color, count) in game.min_set().0 {
+                let max_count = bag.0.entry(color).or_default();
+                *max_count = max(*max_count, count);
+            }
+        }
+        bag
+    }
+
+    /// For each colour, how many draws showed each count of its cubes. Draws
+    /// without the colour aren't counted.
+    pub fn distribution(games: &[Game]) -> BTreeMap<String, BTreeMap<u32, usize>> {
+        let mut res: BTreeMap<String, BTreeMap<u32, usize>> = BTreeMap::new();
+        for draw in games.iter().flat_map(|g| &g.cubes) {
+            for (color, &count) in &draw.0 {
+                *res.entry(color.clone())
+                    .or_default()
+                    .entry(count)
+                    .or_default() += 1;
+            }
+        }
+        res
+    }
+
+    /// Games impossible with `bag`, each with the colours it needs more cubes
+    /// of than the bag has.
+    pub fn bottlenecks(games: &[Game], bag: &Cubes) -> Vec<(u32, Vec<String>)> {
+        games
+            .iter()
+            .filter_map(|game| {
+                let colors: Vec<String> = game
+                    .min_set()
+                    .0
+                    .into_iter()
+                    .filter(|(color, count)| *count > bag.get(color))
+                    .map(|(color, _)| color)
+                    .collect();
+                (!colors.is_empty()).then_some((game.index, colors))
+            })
+            .collect()
+    }
+
+    /// Cubes added to a bag and the games which become possible.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Change {
+        pub added: Cubes,
+        pub games: BTreeSet<u32>,
+    }
+
+    impl Change {
+        /// Number of added cubes.
+        pub fn size(&self) -> u32 {
+            self.added.0.values().sum()
+        }
+    }
+
+    /// Change of `bag` adding the fewest cubes which makes at least `n` more
+    /// games possible, `None` if fewer games are impossible. Tries every
+    /// count of each blocking colour which some game needs, so it takes time
+    /// exponential in the number of colours.
+    pub fn smallest_change(games: &[Game], bag: &Cubes, n: usize) -> Option<Change> {
+        let blocked: Vec<(u32, Cubes)> = games
+            .iter()
+            .map(|g| (g.index, g.min_set()))
+            .filter(|(_, need)| !need.fits(bag))
+            .collect();
+        if n > blocked.len() {
+            return None;
+        }
+
+        // Counts worth trying for each colour which blocks a game.
+        let mut levels: BTreeMap<&str, BTreeSet<u32>> = BTreeMap::new();
+        for (_, need) in &blocked {
+            for (color, &count) in &need.0 {
+                if count > bag.get(color) {
+                    levels
+                        .entry(color)
+                        .or_insert_with(|| BTreeSet::from([bag.get(color)]))
+                        .insert(count);
+                }
+            }
+        }
+        let levels: Vec<(&str, Vec<u32>)> = levels
+            .into_iter()
+            .map(|(color, counts)| (color, counts.into_iter().collect()))
+            .collect();
+
+        let mut best: Option<Change> = None;
+        let mut choice = vec![0; levels.len()];
+        loop {
+            let mut tried = bag.clone();
+            for ((color, counts), &i) in levels.iter().zip(&choice) {
+                tried.0.insert(color.to_string(), counts[i]);
+            }
+            let admitted: BTreeSet<u32> = blocked
+                .iter()
+                .filter(|(_, need)| need.fits(&tried))
+                .map(|(index, _)| *index)
+                .collect();
+
+            if admitted.len() >= n {
+                let added = Cubes(
+                    tried
+                        .0
+                        .iter()
+                        .filter(|(color, &count)| count > bag.get(color))
+                        .map(|(color, &count)| (color.clone(), count - bag.get(color)))
+                        .collect(),
+                );
+                let change = Change {
+                    added,
+                    games: admitted,
+                };
+                let better = match &best {
+                    Some(best) => {
+                        (change.size(), Reverse(change.games.len()))
+                            < (best.size(), Reverse(best.games.len()))
+                    }
+                    None => true,
+                };
+                if better {
+                    best = Some(change);
+                }
+            }
+
+            // Next combination of counts, like an odometer.
+            let mut i = 0;
+            while i < choice.len() && choice[i] + 1 == levels[i].1.len() {
+                choice[i] = 0;
+                i += 1;
+            }
+            if i == choice.len() {
+                break;
+            }
+            choice[i] += 1;
+        }
+        best
+    }
+
+    /// Games possible with `bag`, the smallest bag for all games, counts of
+    /// cubes shown per colour, colours blocking the impossible games and the
+    /// smallest changes of the bag admitting up to `most` more games.
+    pub fn report(games: &[Game], bag: &Cubes, most: usize) -> String {
+        let possible = games.iter().filter(|g| g.min_set().fits(bag)).count();
+        let mut res = format!(
+            "bag: {}\n{} of {} games possible\nbag for all games: {}\n",
+            bag,
+            possible,
+            games.len(),
+            min_bag(games)
+        );
+
+        res += "\ndraws per color:\n";
+        for (color, counts) in distribution(games) {
+            let draws: usize = counts.values().sum();
+            let cubes: u64 = counts.iter().map(|(&c, &n)| u64::from(c) * n as u64).sum();
+            let histogram: Vec<String> = counts
+                .iter()
+                .map(|(count, n)| format!("{}: {}", count, n))
+                .collect();
+            res += &format!(
+                "  {}: {} draws, mean {:.2} cubes\n    {}\n",
+                color,
+                draws,
+                cubes as f64 / draws as f64,
+                histogram.join(", ")
+            );
+        }
+
+        let bottlenecks = bottlenecks(games, bag);
+        res += "\nbottlenecks:\n";
+        let mut blocking: BTreeMap<&str, usize> = BTreeMap::new();
+        for (_, colors) in &bottlenecks {
+            for color in colors {
+                *blocking.entry(color).or_default() += 1;
+            }
+        }
+        for (color, n) in blocking {
+            res += &format!("  {}: {} blocked\n", color, n);
+        }
+        for (index, colors) in &bottlenecks {
+            res += &format!("  game {}: {}\n", index, colors.join(", "));
+        }
+
+        res += "\nsmallest changes:\n";
+        for n in 1..=most.min(bottlenecks.len()) {
+            if let Some(change) = smallest_change(games, bag, n) {
+                let added: Vec<String> = change
+                    .added
+                    .0
+                    .iter()
+                    .map(|(color, count)| format!("+{} {}", count, color))
+                    .collect();
+                let admitted: Vec<String> = change.games.iter().map(|n| n.to_string()).collect();
+                res += &format!(
+                    "  {} more: {} -> games {}\n",
+                    n,
+                    added.join(", "),
+                    admitted.join(", ")
+                );
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day2::solve1(EXAMPLE), Ok(8));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day2::solve2(EXAMPLE), Ok(2286));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day2>(2000);
+    }
+
+    #[test]
+    fn test_stream() {
+        testing::stream::<Day2>(2000);
+    }
+
+    #[test]
+    fn test_colors() {
+        let input = Day2::parse(
+            "Game 1: 3 red, 2 teal; 1 gold\n\
+             Game 2: 5 red; 4 teal, 9 blue\n\
+             Game 3: 1 gold",
+        )
+        .unwrap();
+
+        let bag = Cubes::parse("3 red, 4 teal, 9 blue").unwrap();
+        assert_eq!(input.feasible(&bag), BTreeSet::from([]));
+        let bag = Cubes::parse("5 red, 4 teal, 1 gold, 9 blue").unwrap();
+        assert_eq!(input.feasible(&bag), BTreeSet::from([1, 2, 3]));
+        let bag = Cubes::parse("1 gold, 3 red, 2 teal").unwrap();
+        assert_eq!(input.feasible(&bag), BTreeSet::from([1, 3]));
+
+        assert_eq!(input.0[0].min_set().power(&bag), 6);
+        assert_eq!(input.0[2].min_set().power(&bag), 0);
+
+        assert_eq!(
+            Cubes::parse("1 red, 2 red").unwrap_err().message(),
+            "repeated color"
+        );
+        assert_eq!(Cubes::parse("1 ").unwrap_err().message(), "invalid color");
+    }
+
+    #[test]
+    fn test_query() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            Day2::query(&input, "12 red, 13 green, 14 blue").unwrap(),
+            "bag: 14 blue, 13 green, 12 red\n\
+             possible games: 1, 2, 5\n\
+             3 of 5 games possible, sum of numbers 8\n"
+        );
+        assert!(Day2::query(&input, "12 red, green").is_err());
+    }
+
+    #[test]
+    fn test_analysis() {
+        let games = Day2::parse(EXAMPLE).unwrap().0;
+        let bag = Cubes::bag();
+
+        assert_eq!(
+            analysis::min_bag(&games),
+            Cubes::parse("20 red, 13 green, 15 blue").unwrap()
+        );
+        assert_eq!(
+            analysis::distribution(&games)["red"],
+            BTreeMap::from([(1, 4), (3, 1), (4, 2), (6, 2), (14, 1), (20, 1)])
+        );
+        assert_eq!(
+            analysis::bottlenecks(&games, &bag),
+            vec![
+                (3, vec!["red".to_string()]),
+                (4, vec!["blue".to_string(), "red".to_string()])
+            ]
+        );
+
+        let change = analysis::smallest_change(&games, &bag, 1).unwrap();
+        assert_eq!(change.added, Cubes::parse("2 red, 1 blue").unwrap());
+        assert_eq!(change.games, BTreeSet::from([4]));
+        assert_eq!(change.size(), 3);
+        let change = analysis::smallest_change(&games, &bag, 2).unwrap();
+        assert_eq!(change.size(), 9);
+        assert_eq!(analysis::smallest_change(&games, &bag, 3), None);
+    }
+
+    #[test]
+    fn test_smallest_change() {
+        let text = Day2::generate(40, &mut common::generate::Rng::new(5));
+        let games = Day2::parse(&text).unwrap().0;
+        let bag = Cubes::bag();
+
+        for n in 1..=5 {
+            let change = analysis::smallest_change(&games, &bag, n).unwrap();
+            assert!(change.games.len() >= n);
+
+            // No bag with fewer added cubes admits as many games.
+            for red in 0..change.size() {
+                for green in 0..change.size() - red {
+                    let blue = change.size() - 1 - red - green;
+                    let mut tried = bag.clone();
+                    for (color, added) in [("red", red), ("green", green), ("blue", blue)] {
+                        *tried.0.get_mut(color).unwrap() += added;
+                    }
+                    let admitted = games
+                        .iter()
+                        .filter(|g| !g.min_set().fits(&bag) && g.min_set().fits(&tried))
+                        .count();
+                    assert!(admitted < n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            Day2::report(&input, 5),
+            "bag: 14 blue, 13 green, 12 red\n\
+             3 of 5 games possible\n\
+             bag for all games: 15 blue, 13 green, 20 red\n\
+             \n\
+             draws per color:\n\
+             \x20 blue: 11 draws, mean 4.55 cubes\n\
+             \x20   1: 3, 2: 1, 3: 1, 4: 1, 5: 1, 6: 3, 15: 1\n\
+             \x20 green: 13 draws, mean 3.69 cubes\n\
+             \x20   1: 2, 2: 4, 3: 4, 5: 1, 8: 1, 13: 1\n\
+             \x20 red: 11 draws, mean 5.55 cubes\n\
+             \x20   1: 4, 3: 1, 4: 2, 6: 2, 14: 1, 20: 1\n\
+             \n\
+             bottlenecks:\n\
+             \x20 blue: 1 blocked\n\
+             \x20 red: 2 blocked\n\
+             \x20 game 3: red\n\
+             \x20 game 4: blue, red\n\
+             \n\
+             smallest changes:\n\
+             \x20 1 more: +1 blue, +2 red -> games 4\n\
+             \x20 2 more: +1 blue, +8 red -> games 3, 4\n"
+        );
+        assert!(Day2::report(&input, 1)
+            .ends_with("smallest changes:\n  1 more: +1 blue, +2 red -> games 4\n"));
+    }
+
+    /// Draw as optional counts of red, green and blue cubes.
+    fn draw() -> impl Strategy<Value = [Option<u32>; 3]> {
+        let count = || prop::option::of(0..20_u32);
+        [count(), count(), count()].prop_filter("empty draw", |d| d.iter().any(Option::is_some))
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day2::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(games in prop::collection::vec(prop::collection::vec(draw(), 1..5), 0..5)) {
+            let lines: Vec<String> = games
+                .iter()
+                .enumerate()
+                .map(|(i, draws)| {
+                    let draws: Vec<String> = draws
+                        .iter()
+                        .map(|draw| {
+                            let cubes: Vec<String> = draw
+                                .iter()
+                                .zip(["red", "green", "blue"])
+                                .filter_map(|(n, color)| n.map(|n| format!("{} {}", n, color)))
+                                .collect();
+                            cubes.join(", ")
+                        })
+                        .collect();
+                    format!("Game {}: {}", i + 1, draws.join("; "))
+                })
+                .collect();
+
+            let input = Day2::parse(&testing::lines(&lines)).unwrap();
+
+            prop_assert_eq!(input.0.len(), games.len());
+            for (i, (game, draws)) in input.0.iter().zip(&games).enumerate() {
+                prop_assert_eq!(game.index as usize, i + 1);
+
+                let parsed: Vec<[u32; 3]> = game.cubes.iter().map(|c| [c.get("red"), c.get("green"), c.get("blue")]).collect();
+                let expected: Vec<[u32; 3]> = draws.iter().map(|d| d.map(|n| n.unwrap_or(0))).collect();
+                prop_assert_eq!(parsed, expected);
+            }
+        }
+    }
+}
@@ -1,110 +1,49 @@
-use std::{
-    cmp::max,
-    fs::File,
-    io::{BufRead, BufReader},
+use clap::{builder::RangedU64ValueParser, Parser};
+use common::{
+    error::Result,
+    input::Args,
+    solution::{self, Solution},
 };
-
-#[derive(Debug)]
-struct Game {
-    index: u32,
-    cubes: Vec<Cubes>,
-}
-
-impl Game {
-    fn parse(s: &str) -> Game {
-        let (game_info, rounds_str) = s.split_once(": ").unwrap();
-        let (_, game_index_str) = game_info.split_once(" ").unwrap();
-
-        return Game {
-            index: game_index_str.parse().unwrap(),
-            cubes: rounds_str.split("; ").map(Cubes::parse).collect(),
-        };
-    }
-
-    fn min_set(&self) -> Cubes {
-        let mut cubes = Cubes {
-            red: 0,
-            green: 0,
-            blue: 0,
-        };
-
-        for c in &self.cubes {
-            cubes.red = max(cubes.red, c.red);
-            cubes.green = max(cubes.green, c.green);
-            cubes.blue = max(cubes.blue, c.blue);
-        }
-
-        return cubes;
-    }
+use day2::Day2;
+
+/// Command line arguments of day 2.
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+
+    /// List the games possible with a bag of cubes, such as "12 red, 13
+    /// green, 14 blue", instead of printing answers.
+    #[arg(long, value_name = "BAG")]
+    query: Option<String>,
+
+    /// Print statistics about the game log instead of answers, with the
+    /// smallest changes of the bag of part 1 making up to N more games
+    /// possible.
+    #[arg(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "5",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    report: Option<usize>,
 }
 
-#[derive(Debug)]
-struct Cubes {
-    red: u32,
-    green: u32,
-    blue: u32,
-}
+fn main() -> Result<()> {
+    let cli = Cli::parse();
 
-impl Cubes {
-    fn parse(s: &str) -> Cubes {
-        let mut cubes = Cubes {
-            red: 0,
-            green: 0,
-            blue: 0,
-        };
-        for part in s.split(", ").into_iter() {
-            let (count_str, color) = part.split_once(" ").unwrap();
-            let count = count_str.parse().unwrap();
-            match color {
-                "red" => cubes.red = count,
-                "green" => cubes.green = count,
-                "blue" => cubes.blue = count,
-                _ => panic!("unexpected color {}", color),
-            }
-        }
-        return cubes;
+    if let Some(bag) = &cli.query {
+        let input = Day2::parse(&cli.args.input.read()?)?;
+        print!("{}", Day2::query(&input, bag)?);
+        return Ok(());
     }
 
-    fn power(&self) -> u32 {
-        self.red * self.green * self.blue
+    if let Some(most) = cli.report {
+        let input = Day2::parse(&cli.args.input.read()?)?;
+        print!("{}", Day2::report(&input, most));
+        return Ok(());
     }
-}
-
-fn main() {
-    let file = match File::open("./files/input.txt") {
-        Ok(file) => file,
-        Err(err) => {
-            eprintln!("{}", err);
-            std::process::exit(1)
-        }
-    };
-
-    let lines = BufReader::new(file).lines();
-
-    let mut games: Vec<Game> = Vec::new();
-
-    for line in lines {
-        match line {
-            Ok(line) => games.push(Game::parse(&line)),
-            Err(err) => {
-                eprintln!("{}", err);
-                std::process::exit(1)
-            }
-        }
-    }
-
-    let part1_answer: u32 = games
-        .iter()
-        .filter(|g| {
-            let bound = g.min_set();
-            bound.red <= 12 && bound.green <= 13 && bound.blue <= 14
-        })
-        .map(|g| g.index)
-        .sum();
-
-    println!("Part 1: {:?}", part1_answer);
-
-    let part2_answer: u32 = games.iter().map(|g| g.min_set().power()).sum();
 
-    println!("Part 1: {:?}", part2_answer);
+    solution::run_with::<Day2>(cli.args)
 }
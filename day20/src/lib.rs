@@ -0,0 +1,735 @@
+use common::{
+    error::{Error, Result},
+    generate::Rng,
+    graph::Graph,
+    math,
+    render::{Canvas, Style},
+    solution::Solution,
+    trace::Trace,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Debug, Display},
+};
+
+/// Machine with all modules in the initial state.
+pub struct Input(pub Machine);
+
+/// Day 20: Pulse Propagation.
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(read_machine(input)?))
+    }
+
+    /// Broadcaster feeding four binary counters of `size` flip-flops, at
+    /// most 16 so the answer fits u64, each resetting at a random odd count
+    /// through a conjunction which signals "rx" through an inverter and a
+    /// final conjunction.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let bits = size.clamp(2, 16);
+        let mut lines = vec!["&out -> rx".to_string()];
+        let mut starts = Vec::new();
+        for i in 0..4 {
+            let cycle = rng.usize(1 << (bits - 1)..1 << bits) | 1;
+            lines.extend(counter(&format!("c{}", i), cycle, bits, "out"));
+            starts.push(format!("c{}f0", i));
+        }
+        lines.push(format!("broadcaster -> {}", starts.join(", ")));
+        rng.shuffle(&mut lines);
+
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        part1::solve(&mut input.0.clone())
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        part2::solve(&mut input.0.clone())
+    }
+
+    /// States of modules after each of the button presses of part 1, with
+    /// modules which changed highlighted.
+    fn trace(input: &Input, trace: &mut Trace) -> Result<()> {
+        let mut machine = input.0.clone();
+        let mut previous = machine.describe();
+        trace.record(draw(0, &previous, &previous));
+
+        for press in 1..=1000 {
+            machine.press(|_, _, _| {});
+            let lines = machine.describe();
+            trace.record(draw(press, &lines, &previous));
+            previous = lines;
+        }
+
+        Ok(())
+    }
+
+    fn graph(input: &Input) -> Result<Graph> {
+        Ok(input.0.graph())
+    }
+}
+
+/// Modules of a binary counter named `name` of `bits` flip-flops which
+/// resets after `cycle` presses, sending a high pulse to `output` then.
+/// `cycle` must be odd and need all `bits`. The counter starts at flip-flop
+/// `<name>f0` and its conjunction `<name>c` sends low pulses to flip-flops of
+/// bits which are 0 in `cycle`, adding up to the overflow.
+fn counter(name: &str, cycle: usize, bits: usize, output: &str) -> Vec<String> {
+    let hub = format!("{}c", name);
+    let inverter = format!("{}i", name);
+    let mut hub_outputs = vec![format!("{}f0", name), inverter.clone()];
+    let mut res = Vec::new();
+
+    for bit in 0..bits {
+        let mut outputs = Vec::new();
+        if bit + 1 < bits {
+            outputs.push(format!("{}f{}", name, bit + 1));
+        }
+        if cycle >> bit & 1 == 1 {
+            outputs.push(hub.clone());
+        } else {
+            hub_outputs.push(format!("{}f{}", name, bit));
+        }
+        res.push(format!("%{}f{} -> {}", name, bit, outputs.join(", ")));
+    }
+    res.push(format!("&{} -> {}", hub, hub_outputs.join(", ")));
+    res.push(format!("&{} -> {}", inverter, output));
+
+    res
+}
+
+/// Number of presses above states of modules, lines which differ from the
+/// previous ones are highlighted.
+fn draw(press: usize, lines: &[String], previous: &[String]) -> Canvas {
+    let text = format!("press {:>4}\n{}\n", press, lines.join("\n"));
+    let mut canvas = Canvas::from_text(&text);
+
+    for (row, (line, prev)) in lines.iter().zip(previous).enumerate() {
+        if line != prev {
+            canvas.mark_all((0..line.len()).map(|col| (row + 1, col)), Style::Highlight);
+        }
+    }
+
+    canvas
+}
+
+/// Presses until the module feeding "rx" gets a low pulse, as the lcm of the
+/// cycles of its inputs.
+pub mod part2 {
+    use super::*;
+
+    pub fn solve(machine: &mut Machine) -> Result<u64> {
+        let feeder = machine
+            .inputs("rx")
+            .first()
+            .ok_or_else(|| Error::new("no module sends pulses to rx"))?
+            .to_string();
+        let outputs = machine.inputs(&feeder);
+        if outputs.is_empty() {
+            return Err(Error::new(format!("no modules feed {}", feeder)));
+        }
+        let mut counts: Vec<u64> = Vec::new();
+
+        for output in outputs {
+            counts.push(part2::count(machine, |from, to, pulse| {
+                from == output && to == feeder && pulse == Pulse::High
+            })?);
+            machine.reset();
+        }
+
+        math::lcm_all(counts).ok_or_else(|| Error::new("number of presses overflows u64"))
+    }
+
+    fn count<F>(machine: &mut Machine, break_when: F) -> Result<u64>
+    where
+        F: Fn(&str, &str, Pulse) -> bool,
+    {
+        let mut c: u64 = 0;
+
+        loop {
+            let mut found = false;
+            machine.press(|from, to, pulse| found |= break_when(from, to, pulse));
+            c += 1;
+
+            if found {
+                break;
+            }
+        }
+        Ok(c)
+    }
+}
+
+/// Product of low and high pulses sent during a thousand presses.
+pub mod part1 {
+    use super::*;
+
+    pub fn solve(machine: &mut Machine) -> Result<u64> {
+        let mut low: u64 = 0;
+        let mut high: u64 = 0;
+
+        for _ in 0..1000 {
+            machine.press(|_, _, pulse| match pulse {
+                Pulse::Low => low += 1,
+                Pulse::High => high += 1,
+            });
+        }
+        Ok(low * high)
+    }
+}
+
+fn read_machine(input: &str) -> Result<Machine> {
+    let mut modules = HashMap::new();
+    let mut io: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (i, line) in input.lines().enumerate() {
+        if let Some((left, right)) = line.split_once(" -> ") {
+            let outputs = right.split(", ").collect::<Vec<&str>>();
+
+            let module: Box<dyn Module> = if left == "broadcaster" {
+                Box::new(Broadcaster::new(outputs.clone()))
+            } else {
+                match left.chars().next() {
+                    Some(c) => match c {
+                        '%' => Box::new(FlipFlop::new(&left[1..], outputs.clone())),
+                        '&' => Box::new(Conjunction::new(&left[1..], outputs.clone())),
+                        _ => return Err(Error::invalid("module", left).at_line(i, line)),
+                    },
+                    None => return Err(Error::invalid("module", left).at_line(i, line)),
+                }
+            };
+
+            for output in outputs {
+                io.entry(output.to_string())
+                    .and_modify(|e| e.push(module.name().to_string()))
+                    .or_insert(vec![module.name().to_string()]);
+            }
+
+            modules.insert(module.name().to_string(), module);
+        } else {
+            return Err(Error::new("expected \"<module> -> <outputs>\"").at_line(i, line));
+        }
+    }
+
+    for (output, inputs) in &io {
+        modules
+            .entry(output.to_string())
+            .and_modify(|e| inputs.iter().for_each(|input| e.add_input(input)));
+    }
+
+    Ok(Machine { modules })
+}
+
+/// Modules by name, wired together by their outputs.
+#[derive(Debug)]
+pub struct Machine {
+    pub modules: HashMap<String, Box<dyn Module>>,
+}
+
+impl Machine {
+    /// Modules with an edge to each of their outputs, shaped by their type.
+    pub fn graph(&self) -> Graph {
+        let mut names: Vec<&String> = self.modules.keys().collect();
+        names.sort();
+
+        let mut res = Graph::directed("machine");
+        for name in names {
+            let module = &self.modules[name];
+            res.node(name, &module.graph_node());
+            for output in module.outputs() {
+                res.edge(name, output, &[]);
+            }
+        }
+        res
+    }
+
+    /// Names of modules sending pulses to `name`, sorted.
+    pub fn inputs(&self, name: &str) -> Vec<String> {
+        let mut res: Vec<String> = self
+            .modules
+            .values()
+            .filter(|m| m.outputs().iter().any(|o| o == name))
+            .map(|m| m.name().to_string())
+            .collect();
+        res.sort();
+        res
+    }
+
+    /// Pushes the button once and delivers pulses until there are none left,
+    /// `on_pulse` is called with the sender, receiver and each pulse.
+    pub fn press<F>(&mut self, mut on_pulse: F)
+    where
+        F: FnMut(&str, &str, Pulse),
+    {
+        let mut signals: VecDeque<(String, String, Pulse)> = VecDeque::new();
+        signals.push_back(("button".to_string(), "broadcaster".to_string(), Pulse::Low));
+
+        while let Some((from, to, pulse)) = signals.pop_front() {
+            on_pulse(&from, &to, pulse);
+            self.modules.entry(to).and_modify(|m| {
+                m.send((&from, pulse)).iter().for_each(|out| {
+                    signals.push_back((m.name().to_string(), out.0.to_string(), out.1))
+                });
+            });
+        }
+    }
+
+    /// Modules with their state, one per line sorted by name.
+    pub fn describe(&self) -> Vec<String> {
+        let mut names: Vec<&String> = self.modules.keys().collect();
+        names.sort();
+        names
+            .iter()
+            .map(|name| self.modules[*name].describe())
+            .collect()
+    }
+
+    /// Puts all modules back into their initial state.
+    pub fn reset(&mut self) {
+        for m in self.modules.values_mut() {
+            m.reset();
+        }
+    }
+}
+
+impl Clone for Machine {
+    fn clone(&self) -> Self {
+        Machine {
+            modules: self
+                .modules
+                .iter()
+                .map(|(name, m)| (name.clone(), m.boxed_clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Pulse sent between modules.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+impl Display for Pulse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pulse::Low => write!(f, "low"),
+            Pulse::High => write!(f, "high"),
+        }
+    }
+}
+
+/// Module of the machine, reacts to pulses by sending pulses to its outputs.
+pub trait Module: Debug {
+    fn name(&self) -> &str;
+    fn outputs(&self) -> &[String];
+    fn add_input(&mut self, name: &str);
+    fn send(&mut self, signal: (&str, Pulse)) -> Vec<(String, Pulse)>;
+    fn reset(&mut self);
+    fn boxed_clone(&self) -> Box<dyn Module>;
+    /// Type prefix, name and state of the module as bits.
+    fn describe(&self) -> String;
+    /// Attributes of the node of the module in the graph of the machine.
+    fn graph_node(&self) -> Vec<(&'static str, &'static str)>;
+}
+
+/// Sends the received pulse to all its outputs.
+#[derive(Debug, Clone)]
+pub struct Broadcaster {
+    pub outputs: Vec<String>,
+}
+
+impl Broadcaster {
+    pub fn new(outputs: Vec<&str>) -> Self {
+        Self {
+            outputs: outputs.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl Module for Broadcaster {
+    fn name(&self) -> &str {
+        "broadcaster"
+    }
+
+    fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    fn add_input(&mut self, _name: &str) {}
+
+    fn send(&mut self, (_, pulse): (&str, Pulse)) -> Vec<(String, Pulse)> {
+        self.outputs
+            .iter()
+            .map(|c| (c.to_string(), pulse))
+            .collect::<Vec<(String, Pulse)>>()
+    }
+
+    fn reset(&mut self) {}
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
+    fn describe(&self) -> String {
+        self.name().to_string()
+    }
+
+    fn graph_node(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("shape", "circle")]
+    }
+}
+
+/// Flips on a low pulse and sends its new state, ignores high pulses.
+#[derive(Debug, Clone)]
+pub struct FlipFlop {
+    pub name: String,
+    pub on: bool,
+    pub outputs: Vec<String>,
+}
+
+impl FlipFlop {
+    pub fn new(name: &str, outputs: Vec<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            on: false,
+            outputs: outputs.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl Module for FlipFlop {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    fn add_input(&mut self, _name: &str) {}
+
+    fn send(&mut self, (_, pulse): (&str, Pulse)) -> Vec<(String, Pulse)> {
+        if pulse == Pulse::Low {
+            self.on = !self.on;
+
+            return self
+                .outputs
+                .iter()
+                .map(|c| {
+                    (
+                        c.to_string(),
+                        if self.on { Pulse::High } else { Pulse::Low },
+                    )
+                })
+                .collect::<Vec<(String, Pulse)>>();
+        }
+
+        vec![]
+    }
+
+    fn reset(&mut self) {
+        self.on = false;
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
+    fn describe(&self) -> String {
+        format!("%{} {}", self.name, self.on as u8)
+    }
+
+    fn graph_node(&self) -> Vec<(&'static str, &'static str)> {
+        let fill = if self.on { "green" } else { "white" };
+        vec![("shape", "box"), ("style", "filled"), ("fillcolor", fill)]
+    }
+}
+
+/// Remembers the last pulse from each input, sends low once all are high.
+#[derive(Debug, Clone)]
+pub struct Conjunction {
+    pub name: String,
+    pub inputs: HashMap<String, Pulse>,
+    pub outputs: Vec<String>,
+}
+
+impl Conjunction {
+    pub fn new(name: &str, outputs: Vec<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            inputs: HashMap::new(),
+            outputs: outputs.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl Module for Conjunction {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    fn add_input(&mut self, name: &str) {
+        self.inputs.insert(name.to_string(), Pulse::Low);
+    }
+
+    fn send(&mut self, (from, pulse): (&str, Pulse)) -> Vec<(String, Pulse)> {
+        self.inputs.insert(from.to_string(), pulse);
+
+        let all_high = self.inputs.values().all(|p| p == &Pulse::High);
+
+        self.outputs
+            .iter()
+            .map(|c| {
+                (
+                    c.to_string(),
+                    if all_high { Pulse::Low } else { Pulse::High },
+                )
+            })
+            .collect::<Vec<(String, Pulse)>>()
+    }
+
+    fn reset(&mut self) {
+        self.inputs
+            .values_mut()
+            .for_each(|v: &mut Pulse| *v = Pulse::Low)
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Module> {
+        Box::new(self.clone())
+    }
+
+    fn describe(&self) -> String {
+        let mut inputs: Vec<(&String, &Pulse)> = self.inputs.iter().collect();
+        inputs.sort_by_key(|(name, _)| *name);
+
+        let memory: String = inputs
+            .iter()
+            .map(|(_, &pulse)| if pulse == Pulse::High { '1' } else { '0' })
+            .collect();
+
+        format!("&{} {}", self.name, memory)
+    }
+
+    fn graph_node(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("shape", "diamond")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{render::Format, testing};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+    const EXAMPLE2: &str = include_str!("../files/example2.txt");
+
+    #[test]
+    fn test_graph() {
+        let graph = Day20::graph(&Day20::parse(EXAMPLE2).unwrap()).unwrap();
+
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 6);
+        assert_eq!(
+            graph.to_dot(),
+            testing::lines(&[
+                "digraph \"machine\" {",
+                "  \"a\" [shape=\"box\", style=\"filled\", fillcolor=\"white\"];",
+                "  \"inv\" [shape=\"diamond\"];",
+                "  \"con\" [shape=\"diamond\"];",
+                "  \"b\" [shape=\"box\", style=\"filled\", fillcolor=\"white\"];",
+                "  \"broadcaster\" [shape=\"circle\"];",
+                "  \"output\";",
+                "  \"a\" -> \"inv\";",
+                "  \"a\" -> \"con\";",
+                "  \"b\" -> \"con\";",
+                "  \"broadcaster\" -> \"a\";",
+                "  \"con\" -> \"output\";",
+                "  \"inv\" -> \"b\";",
+                "}",
+            ])
+        );
+    }
+
+    #[test]
+    fn test_broadcaster() {
+        let mut m = Broadcaster::new(vec![&"a", &"b"]);
+
+        assert_eq!(
+            m.send(("", Pulse::High)),
+            vec![
+                ("a".to_string(), Pulse::High),
+                ("b".to_string(), Pulse::High)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flipflop() {
+        let mut m = FlipFlop::new("flipflop", vec![&"a", &"b"]);
+
+        assert_eq!(m.send(("", Pulse::High)), vec![]);
+
+        assert_eq!(
+            m.send(("", Pulse::Low)),
+            vec![
+                ("a".to_string(), Pulse::High),
+                ("b".to_string(), Pulse::High)
+            ]
+        );
+
+        assert_eq!(m.send(("", Pulse::High)), vec![]);
+
+        assert_eq!(
+            m.send(("", Pulse::Low)),
+            vec![("a".to_string(), Pulse::Low), ("b".to_string(), Pulse::Low)]
+        );
+
+        assert_eq!(m.send(("", Pulse::High)), vec![]);
+    }
+
+    #[test]
+    fn test_conjunction() {
+        let mut m = Conjunction::new("conjunction", vec![&"a", &"b"]);
+
+        m.add_input("x");
+        m.add_input("y");
+
+        assert_eq!(
+            m.send(("x", Pulse::High)),
+            vec![
+                ("a".to_string(), Pulse::High),
+                ("b".to_string(), Pulse::High)
+            ]
+        );
+
+        assert_eq!(
+            m.send(("y", Pulse::High)),
+            vec![("a".to_string(), Pulse::Low), ("b".to_string(), Pulse::Low)]
+        );
+
+        assert_eq!(
+            m.send(("x", Pulse::Low)),
+            vec![
+                ("a".to_string(), Pulse::High),
+                ("b".to_string(), Pulse::High)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day20::solve1(EXAMPLE), Ok(32000000));
+        assert_eq!(Day20::solve1(EXAMPLE2), Ok(11687500));
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut lines = vec![
+            "broadcaster -> af0, bf0".to_string(),
+            "&out -> rx".to_string(),
+        ];
+        lines.extend(counter("a", 5, 3, "out"));
+        lines.extend(counter("b", 7, 3, "out"));
+
+        assert_eq!(Day20::solve2(&testing::lines(&lines)), Ok(35));
+        assert!(Day20::solve2(EXAMPLE).is_err());
+        assert_eq!(
+            Day20::solve2("broadcaster -> a\n%a -> a\n&out -> rx\n")
+                .unwrap_err()
+                .message(),
+            "no modules feed out"
+        );
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day20>(10);
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = Day20::parse(EXAMPLE2).unwrap();
+        let mut trace = Trace::new(20);
+        Day20::trace(&input, &mut trace).unwrap();
+
+        let frames: Vec<Canvas> = trace.frames().collect();
+        assert_eq!(frames.len(), 1001);
+
+        let lines = |frame: &Canvas| -> Vec<String> {
+            let text = frame.render(Format::Ascii);
+            text.lines().map(|l| l.trim_end().to_string()).collect()
+        };
+        assert_eq!(
+            lines(&frames[1]),
+            [
+                "press    1",
+                "%a 1",
+                "%b 1",
+                "broadcaster",
+                "&con 11",
+                "&inv 1"
+            ]
+        );
+        assert_eq!(
+            lines(&frames[2]),
+            [
+                "press    2",
+                "%a 0",
+                "%b 1",
+                "broadcaster",
+                "&con 01",
+                "&inv 0"
+            ]
+        );
+        // Only the modules which changed are highlighted.
+        assert_eq!(frames[2].style((1, 0)), Some(Style::Highlight));
+        assert_eq!(frames[2].style((2, 0)), None);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day20::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            modules in prop::collection::btree_map("[a-z]{2}", ("[%&]", prop::collection::vec("[a-z]{2}", 1..4)), 0..8),
+            broadcast in prop::collection::vec("[a-z]{2}", 1..4),
+        ) {
+            let mut lines = vec![format!("broadcaster -> {}", broadcast.join(", "))];
+            for (name, (kind, outputs)) in &modules {
+                lines.push(format!("{}{} -> {}", kind, name, outputs.join(", ")));
+            }
+
+            let machine = Day20::parse(&testing::lines(&lines)).unwrap().0;
+
+            prop_assert_eq!(machine.modules.len(), modules.len() + 1);
+            prop_assert_eq!(machine.modules["broadcaster"].outputs(), &broadcast[..]);
+            for (name, (kind, outputs)) in &modules {
+                let module = &machine.modules[name];
+                let debug = format!("{:?}", module);
+                let expected = if kind == "%" { "FlipFlop" } else { "Conjunction" };
+
+                prop_assert_eq!(module.name(), name);
+                prop_assert!(debug.starts_with(expected), "{}", debug);
+                prop_assert_eq!(module.outputs(), &outputs[..]);
+            }
+        }
+    }
+}
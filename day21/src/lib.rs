@@ -0,0 +1,397 @@
+use common::{
+    error::{Error, Result},
+    generate::Rng,
+    render::{Canvas, Style},
+    solution::Solution,
+    trace::Trace,
+};
+use grid::{Grid, Pos};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Map of garden plots and rocks.
+pub type Input = Map;
+
+/// Day 21: Step Counter.
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        input.parse()
+    }
+
+    /// Map of 131 x 131, the only size the steps of part 2 fit, with up to
+    /// `size` single rocks. Like in the real input, the border, the row and
+    /// column of the start and the diamond reached in 65 steps stay clear.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const SIZE: usize = 131;
+        let centre = Pos::new(SIZE / 2, SIZE / 2);
+        let mut map: Map = Grid::filled(SIZE, SIZE, '.');
+        map[centre] = 'S';
+
+        for _ in 0..size {
+            let pos = Pos::new(rng.usize(1..SIZE - 1), rng.usize(1..SIZE - 1));
+            let clear = pos.row == centre.row
+                || pos.col == centre.col
+                || pos.manhattan(&centre).abs_diff(SIZE / 2) <= 1;
+            // Single rocks only, so every plot stays reachable.
+            let crowded = (pos.row - 1..=pos.row + 1)
+                .any(|row| (pos.col - 1..=pos.col + 1).any(|col| map[Pos::new(row, col)] == '#'));
+            if !clear && !crowded {
+                map[pos] = '#';
+            }
+        }
+
+        map.to_string()
+    }
+
+    fn part1(map: &Input) -> Result<usize> {
+        let start = map.position(|&c| c == 'S').ok_or("no start")?;
+
+        Ok(part1::solve(map, start, 64))
+    }
+
+    fn part2(map: &Input) -> Result<usize> {
+        let start = map.position(|&c| c == 'S').ok_or("no start")?;
+
+        part2::solve(map, start)
+    }
+
+    /// Plots reachable in the steps of part 1 drawn as "O".
+    fn visualize(map: &Input) -> Result<Canvas> {
+        let start = map.position(|&c| c == 'S').ok_or("no start")?;
+
+        Ok(draw(map, start, &part1::reachable(map, start, 64)))
+    }
+
+    /// Plots reachable in each number of steps of part 1.
+    fn trace(map: &Input, trace: &mut Trace) -> Result<()> {
+        let start = map.position(|&c| c == 'S').ok_or("no start")?;
+
+        part1::walk(map, start, 64, |plots| {
+            trace.record(draw(map, start, plots))
+        });
+
+        Ok(())
+    }
+}
+
+/// Garden plots drawn as "O".
+fn draw(map: &Map, start: Pos, plots: &HashSet<Pos>) -> Canvas {
+    let mut canvas = map.canvas();
+
+    for &pos in plots {
+        canvas.draw(pos, 'O', Style::Visited);
+    }
+    canvas.mark(start, Style::Highlight);
+
+    canvas
+}
+
+/*
+┌─────┬─────┬──╳──┬─────┬─────┐
+│#####│*****│#╱#╲#│*****│#####│
+│#####│*****│╱###╲│*****│#####│
+│#####│*****╳#####╳*****│#####│
+│#####│****╱│#####│╲****│#####│
+│#####│***╱*│#####│*╲***│#####│
+├─────┼──╳──┼─────┼──╳──┼─────┤
+│*****│#╱###│*****│###╲#│*****│
+│*****│╱####│*****│####╲│*****│
+│*****╳#####│*****│#####╳*****│
+│****╱│#####│*****│#####│╲****│
+│***╱*│#####│*****│#####│*╲***│
+├──╳──┼─────┼─────┼─────┼──╳──┤
+│#╱###│*****│#####│*****│###╲#│
+│╱####│*****│#####│*****│####╲│
+╲#####│*****│#####│*****│#####▲
+│╲####│*****│#####│*****│####╱│
+│#╲###│*****│#####│*****│###╱#│
+├──╳──┼─────┼─────┼─────┼──╳──┤
+│***╲*│#####│*****│#####│*╱***│
+│****╲│#####│*****│#####│╱****│
+│*****╳#####│*****│#####╳*****│
+│*****│╲####│*****│####╱│*****│
+│*****│#╲###│*****│###╱#│*****│
+├─────┼──╳──┼─────┼──╳──┼─────┤
+│#####│***╲*│#####│*╱***│#####│
+│#####│****╲│#####│╱****│#####│
+│#####│*****╳#####╳*****│#####│
+│#####│*****│╲###╱│*****│#####│
+│#####│*****│#╲#╱#│*****│#####│
+└─────┴─────┴──╱──┴─────┴─────┘
+*/
+
+/// Plots reachable on the infinitely repeated map, extrapolated from whole
+/// map copies.
+pub mod part2 {
+
+    use super::*;
+
+    /// Plots reachable in the 26501365 steps of the puzzle.
+    pub fn solve(map: &Map, start: Pos) -> Result<usize> {
+        count(map, start, 26501365)
+    }
+
+    /// Plots of the infinitely repeated map reachable in exactly `steps`
+    /// steps, counted from the copies of the map the steps cover. Works on
+    /// maps shaped like the real input, with an empty row and column through
+    /// the start in the centre, and odd `steps` which end at the edge of a
+    /// copy an even number of copies away.
+    pub fn count(map: &Map, start: Pos, steps: usize) -> Result<usize> {
+        let mut points: VecDeque<(Pos, usize)> = VecDeque::new();
+        let mut seen: HashMap<Pos, usize> = HashMap::new();
+        points.push_back((start, 0));
+
+        while let Some((pos, dist)) = points.pop_front() {
+            if seen.contains_key(&pos) {
+                continue;
+            }
+            seen.insert(pos, dist);
+            for next in gardens(map, pos) {
+                points.push_back((next, dist + 1))
+            }
+        }
+
+        let even = seen.values().filter(|d| *d % 2 == 0).count();
+        let odd = seen.values().filter(|d| *d % 2 == 1).count();
+
+        let half = map.cols() / 2;
+        if half != 65 || start != Pos::new(half, half) {
+            return Err(Error::new("expected 131x131 map with start in the centre"));
+        }
+        if steps < half || !(steps - half).is_multiple_of(2 * map.cols()) {
+            return Err(Error::new(format!(
+                "expected {} steps and a multiple of {} more",
+                half,
+                2 * map.cols()
+            )));
+        }
+
+        let n = (steps - half) / map.cols();
+
+        let even_corners = seen
+            .iter()
+            .filter(|&(pos, dist)| dist % 2 == 0 && pos.manhattan(&start) > half)
+            .count();
+
+        let odd_corners = seen
+            .iter()
+            .filter(|&(pos, dist)| dist % 2 == 1 && pos.manhattan(&start) > half)
+            .count();
+
+        Ok((n + 1) * (n + 1) * odd + n * n * even - (n + 1) * odd_corners + n * even_corners)
+    }
+
+    /// Plots of the infinitely repeated map reachable in exactly `steps`
+    /// steps, by walking all of them. Reference for [`count`] on any map.
+    pub fn count_tiled(map: &Map, start: Pos, steps: usize) -> usize {
+        let (rows, cols) = (map.rows() as isize, map.cols() as isize);
+        let is_garden = |(row, col): (isize, isize)| {
+            map[Pos::new(row.rem_euclid(rows) as usize, col.rem_euclid(cols) as usize)] != '#'
+        };
+
+        let start = (start.row as isize, start.col as isize);
+        let mut seen: HashSet<(isize, isize)> = HashSet::from([start]);
+        let mut points: VecDeque<((isize, isize), usize)> = VecDeque::from([(start, 0)]);
+        let mut res = 0;
+
+        while let Some(((row, col), dist)) = points.pop_front() {
+            // Plots reached in fewer steps of the same parity can be returned
+            // to by stepping back and forth.
+            if dist % 2 == steps % 2 {
+                res += 1;
+            }
+            if dist == steps {
+                continue;
+            }
+            for next in [
+                (row - 1, col),
+                (row + 1, col),
+                (row, col - 1),
+                (row, col + 1),
+            ] {
+                if is_garden(next) && seen.insert(next) {
+                    points.push_back((next, dist + 1));
+                }
+            }
+        }
+
+        res
+    }
+}
+
+/// Plots reachable in exactly the given number of steps.
+pub mod part1 {
+    use super::*;
+
+    /// Number of garden plots reachable in exactly `steps` steps.
+    pub fn solve(map: &Map, start: Pos, steps: usize) -> usize {
+        reachable(map, start, steps).len()
+    }
+
+    /// Garden plots reachable in exactly `steps` steps.
+    pub fn reachable(map: &Map, start: Pos, steps: usize) -> HashSet<Pos> {
+        walk(map, start, steps, |_| {})
+    }
+
+    /// Garden plots reachable in exactly `steps` steps, `on_step` is called
+    /// with plots reachable in each number of steps from 0 on.
+    pub fn walk<F>(map: &Map, start: Pos, steps: usize, mut on_step: F) -> HashSet<Pos>
+    where
+        F: FnMut(&HashSet<Pos>),
+    {
+        let mut points: VecDeque<Pos> = VecDeque::new();
+        let mut seen: HashSet<Pos> = HashSet::new();
+        points.push_back(start);
+
+        for _ in 0..=steps {
+            seen.clear();
+            let mut count = points.len();
+
+            while count > 0 {
+                count -= 1;
+                if let Some(pos) = points.pop_front() {
+                    if !seen.insert(pos) {
+                        continue;
+                    }
+                    points.extend(gardens(map, pos));
+                }
+            }
+            on_step(&seen);
+        }
+
+        seen
+    }
+}
+
+/// Map of garden plots "." and rocks "#", with the start "S".
+pub type Map = Grid<char>;
+
+/// Neighbour garden plots of the given position, start is a garden plot too.
+pub fn gardens(map: &Map, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+    map.neighbours(pos)
+        .filter(|&p| map[p] == '.' || map[p] == 'S')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{render::Format, testing};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        // Published answer is for 6 steps.
+        let map = Day21::parse(EXAMPLE).unwrap();
+        let start = map.position(|&c| c == 'S').unwrap();
+        assert_eq!(part1::solve(&map, start, 6), 16);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let map = Day21::parse(EXAMPLE).unwrap();
+        let start = map.position(|&c| c == 'S').unwrap();
+        let canvas = Day21::visualize(&map).unwrap();
+
+        let plots = map
+            .positions()
+            .filter(|&pos| canvas.style(pos.into()).is_some())
+            .count();
+        assert_eq!(plots, part1::solve(&map, start, 64));
+        assert_eq!(canvas.style(start.into()), Some(Style::Highlight));
+    }
+
+    #[test]
+    fn test_trace() {
+        let map = Day21::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(21);
+        Day21::trace(&map, &mut trace).unwrap();
+
+        let frames: Vec<Canvas> = trace.frames().collect();
+        assert_eq!(frames.len(), 65);
+        // Plots drawn after 6 steps match the published example.
+        assert_eq!(
+            frames[6].render(Format::Ascii),
+            testing::lines(&[
+                "...........",
+                ".....###.#.",
+                ".###.##.O#.",
+                ".O#O#O.O#..",
+                "O.O.#.#.O..",
+                ".##O.O####.",
+                ".##.O#O..#.",
+                ".O.O.O.##..",
+                ".##.#.####.",
+                ".##O.##.##.",
+                "...........",
+            ])
+        );
+        assert_eq!(frames.last(), Some(&Day21::visualize(&map).unwrap()));
+    }
+
+    #[test]
+    fn test_part2() {
+        // Solution relies on the shape of the real input: empty row and column
+        // through the start in the centre of 131x131 map, which the example lacks.
+        assert!(Day21::solve2(EXAMPLE).is_err());
+
+        // Published answers for the example, by walking the repeated map.
+        let map = Day21::parse(EXAMPLE).unwrap();
+        let start = map.position(|&c| c == 'S').unwrap();
+        for (steps, plots) in [(6, 16), (10, 50), (50, 1594), (100, 6536), (500, 167004)] {
+            assert_eq!(
+                part2::count_tiled(&map, start, steps),
+                plots,
+                "{} steps",
+                steps
+            );
+        }
+    }
+
+    #[test]
+    fn test_part2_tiled() {
+        for seed in 0..3 {
+            let map = Day21::parse(&Day21::generate(
+                2000,
+                &mut common::generate::Rng::new(seed),
+            ))
+            .unwrap();
+            let start = map.position(|&c| c == 'S').unwrap();
+
+            for steps in [65, 65 + 2 * 131, 65 + 4 * 131] {
+                assert_eq!(
+                    part2::count(&map, start, steps),
+                    Ok(part2::count_tiled(&map, start, steps)),
+                    "seed {} steps {}",
+                    seed,
+                    steps
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day21>(2000);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day21::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::grid(".#S", 10)) {
+            prop_assert_eq!(Day21::parse(&s).unwrap().to_string(), s);
+        }
+    }
+}
@@ -0,0 +1,570 @@
+use common::{
+    error::{self, Context, Error, Result},
+    generate::Rng,
+    graph::Graph,
+    parallel,
+    render::{Canvas, Style},
+    solution::Solution,
+    trace::Trace,
+};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Snapshot of falling bricks, ordered by height.
+pub struct Input(pub BTreeSet<Brick>);
+
+/// Day 22: Sand Slabs.
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(read_bricks(input)?))
+    }
+
+    /// `size` bricks of up to 4 cubes over a 10 x 10 area, each starting
+    /// above the top of the previous one so none overlap.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut res = String::new();
+        let mut z = 1;
+        for _ in 0..size {
+            let (x, y) = (rng.usize(0..10), rng.usize(0..10));
+            let length = rng.usize(0..4);
+            let (x2, y2, z2) = match rng.usize(0..3) {
+                0 => ((x + length).min(9), y, z),
+                1 => (x, (y + length).min(9), z),
+                _ => (x, y, z + length),
+            };
+            res.push_str(&format!("{},{},{}~{},{},{}\n", x, y, z, x2, y2, z2));
+            z = z2 + rng.usize(1..3);
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        let (settled, supports, supported_by) = drop(&mut input.0.clone());
+
+        Ok(safe_to_disintegrate(&settled, &supports, &supported_by))
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        let (settled, supports, supported_by) = drop(&mut input.0.clone());
+
+        Ok(chain_reaction(&settled, &supports, &supported_by))
+    }
+
+    /// Side view of bricks falling one by one, settled bricks are coloured and
+    /// the last one landed is highlighted.
+    fn trace(input: &Input, trace: &mut Trace) -> Result<()> {
+        let height = input.0.iter().map(|b| b.second.z).max().unwrap_or(0);
+        let width = input
+            .0
+            .iter()
+            .map(|b| b.first.x.max(b.second.x))
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        trace.record(side_view(&BTreeSet::new(), &input.0, None, height, width));
+        drop_with(&mut input.0.clone(), |settled, falling, brick| {
+            trace.record(side_view(settled, falling, Some(brick), height, width))
+        });
+
+        Ok(())
+    }
+
+    fn graph(input: &Input) -> Result<Graph> {
+        let (settled, supports, _) = drop(&mut input.0.clone());
+
+        Ok(graph(&settled, &supports))
+    }
+}
+
+/// Settled bricks with an edge to each brick they support, bricks lying on the
+/// ground are supported by a "ground" node. Nodes are labelled with the letter
+/// of the side view and the settled position.
+pub fn graph(settled: &BTreeSet<Brick>, supports: &Support) -> Graph {
+    let mut res = Graph::directed("support");
+    res.node("ground", &[("shape", "box")]);
+
+    for brick in settled {
+        let id = brick.id.to_string();
+        let label = format!(
+            "{} {},{},{}~{},{},{}",
+            (b'A' + (brick.id % 26) as u8) as char,
+            brick.first.x,
+            brick.first.y,
+            brick.first.z,
+            brick.second.x,
+            brick.second.y,
+            brick.second.z
+        );
+        res.node(&id, &[("label", &label)]);
+        if brick.first.z == 1 {
+            res.edge("ground", &id, &[]);
+        }
+    }
+
+    for (id, above) in supports {
+        for other in above {
+            res.edge(&id.to_string(), &other.to_string(), &[]);
+        }
+    }
+
+    res
+}
+
+/// Bricks seen along the y axis, as drawn in the puzzle: x grows to the right,
+/// z upwards and the ground is the bottom row. Bricks are labelled with letters,
+/// "?" marks more bricks behind each other.
+fn side_view(
+    settled: &BTreeSet<Brick>,
+    falling: &BTreeSet<Brick>,
+    landed: Option<&Brick>,
+    height: usize,
+    width: usize,
+) -> Canvas {
+    let mut canvas = Canvas::new(height + 1, width, '.');
+    for col in 0..width {
+        canvas.set((height, col), '-');
+    }
+
+    let bricks = settled
+        .iter()
+        .map(|b| (b, Style::Visited))
+        .chain(falling.iter().map(|b| (b, Style::Path)));
+
+    for (brick, style) in bricks {
+        let style = if Some(brick) == landed {
+            Style::Highlight
+        } else {
+            style
+        };
+        let label = (b'A' + (brick.id % 26) as u8) as char;
+
+        for x in brick.first.x.min(brick.second.x)..=brick.first.x.max(brick.second.x) {
+            for z in brick.first.z..=brick.second.z {
+                let pos = (height - z, x);
+                let ch = match canvas.get(pos) {
+                    Some('.') => label,
+                    _ => '?',
+                };
+                canvas.draw(pos, ch, style);
+            }
+        }
+    }
+
+    canvas
+}
+
+/// Sum of bricks which would fall when each brick is disintegrated.
+pub fn chain_reaction(
+    settled: &BTreeSet<Brick>,
+    supports: &BTreeMap<u32, BTreeSet<u32>>,
+    supported_by: &BTreeMap<u32, BTreeSet<u32>>,
+) -> u64 {
+    let ids: Vec<u32> = settled.iter().map(|brick| brick.id).collect();
+
+    parallel::map(&ids, |&id| count(id, supports, supported_by))
+        .into_iter()
+        .sum()
+}
+
+/// Number of bricks which fall when the brick `start_id` is disintegrated.
+pub fn count(
+    start_id: u32,
+    supports: &BTreeMap<u32, BTreeSet<u32>>,
+    supported_by: &BTreeMap<u32, BTreeSet<u32>>,
+) -> u64 {
+    let mut removed = BTreeSet::new();
+    let mut newly_removed = VecDeque::new();
+
+    removed.insert(start_id);
+    newly_removed.push_back(start_id);
+
+    while !newly_removed.is_empty() {
+        let count = newly_removed.len();
+        let mut i = 0;
+        let mut seen = BTreeSet::new();
+        while let Some(id) = newly_removed.pop_front() {
+            if !seen.insert(id) {
+                break;
+            }
+            if let Some(supports) = supports.get(&id) {
+                supports
+                    .iter()
+                    .filter(|id| {
+                        supported_by
+                            .get(id)
+                            .is_some_and(|ids| ids.difference(&removed).count() == 0)
+                    })
+                    .for_each(|&id| {
+                        newly_removed.push_back(id);
+                    });
+            }
+            i += 1;
+
+            if i == count {
+                break;
+            }
+        }
+        newly_removed.iter().for_each(|&id| {
+            removed.insert(id);
+        })
+    }
+    (removed.len() - 1) as u64
+}
+
+/// Number of bricks not being the only support of any other brick.
+pub fn safe_to_disintegrate(
+    settled: &BTreeSet<Brick>,
+    supports: &BTreeMap<u32, BTreeSet<u32>>,
+    supported_by: &BTreeMap<u32, BTreeSet<u32>>,
+) -> u64 {
+    let mut res = 0;
+
+    for brick in settled.iter().rev() {
+        if let Some(supports) = supports.get(&brick.id) {
+            if supports
+                .iter()
+                .all(|id| supported_by.get(id).is_some_and(|ids| ids.len() > 1))
+            {
+                res += 1;
+            }
+        } else {
+            res += 1;
+        }
+    }
+
+    res
+}
+
+/// Brick ids mapped to ids of bricks above or below them.
+pub type Support = BTreeMap<u32, BTreeSet<u32>>;
+
+/// Lets the bricks fall, returns them settled with the bricks each one supports
+/// and the bricks each one is supported by.
+pub fn drop(bricks: &mut BTreeSet<Brick>) -> (BTreeSet<Brick>, Support, Support) {
+    drop_with(bricks, |_, _, _| {})
+}
+
+/// Lets the bricks fall from the lowest one like [`drop`], `on_settle` is
+/// called after each brick lands with the settled bricks, the ones still
+/// falling and the brick which just landed.
+pub fn drop_with<F>(
+    bricks: &mut BTreeSet<Brick>,
+    mut on_settle: F,
+) -> (BTreeSet<Brick>, Support, Support)
+where
+    F: FnMut(&BTreeSet<Brick>, &BTreeSet<Brick>, &Brick),
+{
+    let mut z_edges: BTreeMap<usize, BTreeSet<Edge>> = BTreeMap::new();
+    let mut settled: BTreeSet<Brick> = BTreeSet::new();
+    let mut supports: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::new();
+    let mut supported_by: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::new();
+
+    while let Some(mut brick) = bricks.pop_first() {
+        let edge = brick.edge();
+        let mut z = brick.first.z;
+
+        loop {
+            if z <= 1 {
+                break;
+            }
+
+            if let Some(intersecting) = z_edges
+                .get(&(z - 1))
+                .map(|edges| edges.iter().filter(|e| e.intersects(&edge)))
+            {
+                let mut count = 0;
+
+                intersecting.for_each(|e| {
+                    // Current brick is supported by all bricks with intersecting edges.
+                    supported_by
+                        .entry(brick.id)
+                        .and_modify(|entry| {
+                            entry.insert(e.id);
+                        })
+                        .or_insert(BTreeSet::from_iter(vec![e.id]));
+
+                    // Brick with intersecting edge support current brick.
+                    supports
+                        .entry(e.id)
+                        .and_modify(|entry| {
+                            entry.insert(brick.id);
+                        })
+                        .or_insert(BTreeSet::from_iter(vec![brick.id]));
+
+                    count += 1;
+                });
+
+                if count > 0 {
+                    break;
+                }
+            }
+
+            z -= 1;
+        }
+
+        brick.second.z -= brick.first.z - z;
+        brick.first.z = z;
+
+        // Save top edge of settled brick.
+        z_edges
+            .entry(brick.second.z)
+            .and_modify(|e| {
+                e.insert(edge);
+            })
+            .or_insert(BTreeSet::from_iter(vec![edge]));
+
+        settled.insert(brick);
+        on_settle(&settled, bricks, &brick);
+    }
+
+    (settled, supports, supported_by)
+}
+
+fn read_bricks(input: &str) -> Result<BTreeSet<Brick>> {
+    let mut bricks: BTreeSet<Brick> = BTreeSet::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let (left, right) = line
+            .split_once('~')
+            .ok_or_else(|| Error::new("expected \"<x,y,z>~<x,y,z>\"").at_line(i, line))?;
+
+        let first = parse_coord(left).at_line(i, line)?;
+        let second = parse_coord(right).at_line(i, line)?;
+
+        if first.z > second.z {
+            return Err(Error::new("brick ends must be ordered by z").at_line(i, line));
+        }
+
+        bricks.insert(Brick::new(i as u32, first, second));
+    }
+
+    Ok(bricks)
+}
+
+fn parse_coord(s: &str) -> Result<Coord3> {
+    let nums = s
+        .split(',')
+        .map(|num| error::parse(num, "coordinate"))
+        .collect::<Result<Vec<usize>>>()?;
+
+    match nums[..] {
+        [x, y, z] => Ok(Coord3::new(x, y, z)),
+        _ => Err(Error::invalid("coordinates", s)),
+    }
+}
+
+/// Brick between two corner cubes, ordered by height.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Brick {
+    pub id: u32,
+    pub first: Coord3,
+    pub second: Coord3,
+}
+
+impl Brick {
+    pub fn new(id: u32, first: Coord3, second: Coord3) -> Self {
+        Brick { id, first, second }
+    }
+
+    fn edge(&self) -> Edge {
+        Edge::new(
+            self.id,
+            Coord2::new(self.first.x, self.first.y),
+            Coord2::new(self.second.x, self.second.y),
+        )
+    }
+}
+
+impl PartialOrd for Brick {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Brick {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.first
+            .z
+            .cmp(&other.first.z)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+struct Edge {
+    id: u32,
+    first: Coord2,
+    second: Coord2,
+}
+
+impl Edge {
+    fn new(id: u32, first: Coord2, second: Coord2) -> Self {
+        Self { id, first, second }
+    }
+
+    fn kind(&self) -> EdgeKind {
+        if self.first.x == self.second.x {
+            EdgeKind::YParallel
+        } else {
+            EdgeKind::XParallel
+        }
+    }
+
+    fn intersects(&self, other: &Edge) -> bool {
+        let (e1, e2) = if self.first > other.first {
+            (other, self)
+        } else {
+            (self, other)
+        };
+
+        match (e1.kind(), e2.kind()) {
+            (EdgeKind::XParallel, EdgeKind::XParallel) => {
+                e1.first.y == e2.first.y && e2.first.x >= e1.first.x && e2.first.x <= e1.second.x
+            }
+            (EdgeKind::YParallel, EdgeKind::YParallel) => {
+                e1.first.x == e2.first.x && e2.first.y >= e1.first.y && e2.first.y <= e1.second.y
+            }
+            (EdgeKind::XParallel, EdgeKind::YParallel) => {
+                e1.first.y >= e2.first.y
+                    && e1.first.y <= e2.second.y
+                    && e2.first.x >= e1.first.x
+                    && e2.first.x <= e1.second.x
+            }
+            (EdgeKind::YParallel, EdgeKind::XParallel) => {
+                e2.first.y >= e1.first.y
+                    && e2.first.y <= e1.second.y
+                    && e1.first.x >= e2.first.x
+                    && e1.first.x <= e2.second.x
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EdgeKind {
+    XParallel,
+    YParallel,
+}
+
+/// Position in the horizontal plane.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Coord2 {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord2 {
+    pub fn new(x: usize, y: usize) -> Coord2 {
+        Coord2 { x, y }
+    }
+}
+
+/// Position of a cube, `z` is the height.
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone, Copy)]
+pub struct Coord3 {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+}
+
+impl Coord3 {
+    pub fn new(x: usize, y: usize, z: usize) -> Coord3 {
+        Coord3 { x, y, z }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{render::Format, testing};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day22::solve1(EXAMPLE), Ok(5));
+    }
+
+    #[test]
+    fn test_graph() {
+        let graph = Day22::graph(&Day22::parse(EXAMPLE).unwrap()).unwrap();
+
+        assert_eq!(graph.node_count(), 8);
+        // A lies on the ground and holds B and C, both hold D and E, which hold
+        // F, which holds G.
+        assert_eq!(graph.edge_count(), 10);
+        let dot = graph.to_dot();
+        assert!(dot.contains("  \"0\" [label=\"A 1,0,1~1,2,1\"];\n"));
+        assert!(dot.contains("  \"ground\" -> \"0\";\n"));
+        assert!(dot.contains("  \"5\" -> \"6\";\n"));
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = Day22::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(22);
+        Day22::trace(&input, &mut trace).unwrap();
+
+        let frames: Vec<Canvas> = trace.frames().collect();
+        assert_eq!(frames.len(), 8);
+        assert_eq!(
+            frames[0].render(Format::Ascii),
+            testing::lines(&[".G.", ".G.", "...", "FFF", "..E", "D..", "CCC", "BBB", ".A.", "---"])
+        );
+        // Side view of the settled bricks from the puzzle.
+        assert_eq!(
+            frames[7].render(Format::Ascii),
+            testing::lines(&["...", "...", "...", ".G.", ".G.", "FFF", "D.E", "???", ".A.", "---"])
+        );
+        assert_eq!(frames[7].style((3, 1)), Some(Style::Highlight));
+        assert_eq!(frames[7].style((5, 1)), Some(Style::Visited));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day22::solve2(EXAMPLE), Ok(7));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day22>(400);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day22::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            bricks in prop::collection::vec(([0..10_usize, 0..10_usize, 1..300_usize], [0..10_usize, 0..10_usize, 0..5_usize]), 0..10)
+        ) {
+            let lines: Vec<String> = bricks
+                .iter()
+                .map(|([x1, y1, z1], [x2, y2, dz])| format!("{},{},{}~{},{},{}", x1, y1, z1, x2, y2, z1 + dz))
+                .collect();
+
+            let mut parsed: Vec<Brick> = Day22::parse(&testing::lines(&lines)).unwrap().0.into_iter().collect();
+            parsed.sort_by_key(|b| b.id);
+
+            let expected: Vec<Brick> = bricks
+                .iter()
+                .enumerate()
+                .map(|(i, &([x1, y1, z1], [x2, y2, dz]))| {
+                    Brick::new(i as u32, Coord3::new(x1, y1, z1), Coord3::new(x2, y2, z1 + dz))
+                })
+                .collect();
+            prop_assert_eq!(parsed, expected);
+        }
+    }
+}
@@ -0,0 +1,451 @@
+use common::{
+    error::Result,
+    generate::Rng,
+    graph::Graph,
+    render::{Canvas, Style},
+    solution::Solution,
+};
+use grid::{Direction, Grid, Pos};
+use std::{
+    cmp::{max, Ordering},
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, VecDeque},
+};
+
+/// Map of hiking trails with start and end found.
+pub struct Input(pub Map);
+
+/// Day 23: A Long Walk.
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(Map::new(input.parse()?)?))
+    }
+
+    /// Lattice of 5 x 5 crossroads `size` tiles apart at most, joined by
+    /// straight trails of which some are missing. Slopes next to crossroads
+    /// lead right and down, like in the real input.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const CROSSROADS: usize = 5;
+        // Positions of rows and columns of crossroads.
+        let lines = |rng: &mut Rng| -> Vec<usize> {
+            (0..CROSSROADS)
+                .scan(1, |pos, i| {
+                    if i > 0 {
+                        *pos += rng.usize(3..size.max(3) + 1);
+                    }
+                    Some(*pos)
+                })
+                .collect()
+        };
+        let (rows, cols) = (lines(rng), lines(rng));
+        let (last_row, last_col) = (rows[CROSSROADS - 1], cols[CROSSROADS - 1]);
+        let mut grid = Grid::filled(last_row + 2, last_col + 2, '#');
+
+        grid[Pos::new(0, 1)] = '.';
+        grid[Pos::new(last_row + 1, last_col)] = '.';
+        for i in 0..CROSSROADS {
+            for j in 0..CROSSROADS {
+                grid[Pos::new(rows[i], cols[j])] = '.';
+                // Trails along the top and right edge always connect the start
+                // with the end.
+                if j + 1 < CROSSROADS && (i == 0 || rng.chance(0.8)) {
+                    for col in cols[j] + 1..cols[j + 1] {
+                        grid[Pos::new(rows[i], col)] = '.';
+                    }
+                    grid[Pos::new(rows[i], cols[j] + 1)] = '>';
+                    grid[Pos::new(rows[i], cols[j + 1] - 1)] = '>';
+                }
+                if i + 1 < CROSSROADS && (j == CROSSROADS - 1 || rng.chance(0.8)) {
+                    for row in rows[i] + 1..rows[i + 1] {
+                        grid[Pos::new(row, cols[j])] = '.';
+                    }
+                    grid[Pos::new(rows[i] + 1, cols[j])] = 'v';
+                    grid[Pos::new(rows[i + 1] - 1, cols[j])] = 'v';
+                }
+            }
+        }
+
+        grid.to_string()
+    }
+
+    fn part1(input: &Input) -> Result<usize> {
+        Ok(longest_slippery_hike(&input.0))
+    }
+
+    fn part2(input: &Input) -> Result<usize> {
+        Ok(longest_dry_hike(&input.0))
+    }
+
+    /// Longest hike of part 1 drawn as "O".
+    fn visualize(input: &Input) -> Result<Canvas> {
+        let map = &input.0;
+        let mut canvas = map.grid.canvas();
+
+        for p in slippery_hike(map) {
+            canvas.draw(p, 'O', Style::Path);
+        }
+        canvas.mark(map.start, Style::Highlight);
+        canvas.mark(map.end, Style::Highlight);
+
+        Ok(canvas)
+    }
+
+    fn graph(input: &Input) -> Result<Graph> {
+        Ok(graph(&input.0))
+    }
+}
+
+/// Crossroads connected by trails, nodes are named "row,col" and edges are
+/// labelled with the length of the trail. Start and end are double circles.
+pub fn graph(map: &Map) -> Graph {
+    let id = |p: &Point| format!("{},{}", p.row, p.col);
+
+    let mut res = Graph::undirected("trails");
+    for p in [map.start, map.end] {
+        res.node(&id(&p), &[("shape", "doublecircle")]);
+    }
+    for (p, neighbors) in crossroads(map) {
+        res.node(&id(&p), &[]);
+        // Each trail is stored at both of its ends.
+        for (q, len) in neighbors.iter().filter(|(q, _)| p < *q) {
+            res.edge(&id(&p), &id(q), &[("label", &len.to_string())]);
+        }
+    }
+    res
+}
+
+/// Crossroads, start and end mapped to the ones reachable from them without
+/// passing another crossroad, with the length of the trail.
+pub fn crossroads(map: &Map) -> BTreeMap<Point, BTreeSet<(Point, usize)>> {
+    let mut edges = map.edges();
+
+    loop {
+        let mut to_modify = None;
+
+        for (&p, neighbors) in edges.iter() {
+            if neighbors.len() == 2 {
+                let mut iter = neighbors.iter();
+                let e1 = *iter.next().unwrap();
+                let e2 = *iter.next().unwrap();
+                to_modify = Some((p, e1, e2));
+                break;
+            }
+        }
+
+        if let Some((p, e1, e2)) = to_modify {
+            if let Some(s) = edges.get_mut(&e1.0) {
+                s.remove(&(p, e1.1));
+                s.insert((e2.0, e1.1 + e2.1));
+            }
+            if let Some(s) = edges.get_mut(&e2.0) {
+                s.remove(&(p, e2.1));
+                s.insert((e1.0, e1.1 + e2.1));
+            }
+            edges.remove(&p);
+        } else {
+            break;
+        }
+    }
+
+    edges
+}
+
+/// Longest hike ignoring slopes, searched on the graph of crossroads.
+pub fn longest_dry_hike(map: &Map) -> usize {
+    let edges = crossroads(map);
+
+    let mut queue = VecDeque::new();
+    let mut seen: BTreeSet<Point> = BTreeSet::new();
+    let mut res = 0;
+
+    queue.push_back((map.start, 0));
+
+    while let Some((p, d)) = queue.pop_back() {
+        if d == 0 && p != map.start {
+            seen.remove(&p);
+            continue;
+        }
+
+        if p == map.end {
+            res = max(res, d);
+            continue;
+        }
+
+        if seen.contains(&p) {
+            continue;
+        }
+
+        seen.insert(p);
+        queue.push_back((p, 0));
+
+        if let Some(s) = edges.get(&p) {
+            s.iter().for_each(|e| {
+                queue.push_back((e.0, e.1 + d));
+            });
+        }
+    }
+
+    res
+}
+
+/// Longest hike which only walks down the slopes.
+pub fn longest_slippery_hike(map: &Map) -> usize {
+    slippery_hike(map).len() - 1
+}
+
+/// Tiles of the longest hike which only walks down the slopes, from the start
+/// to the end.
+pub fn slippery_hike(map: &Map) -> Vec<Point> {
+    let mut max_id = 0;
+
+    let mut next_id = || {
+        max_id += 1;
+        max_id
+    };
+
+    let mut came_from: HashMap<Node, Node> = HashMap::new();
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Node::new(map.start, 0));
+
+    while let Some(cur_node) = queue.pop() {
+        let cur_p = cur_node.point;
+
+        if cur_p == map.end {
+            break;
+        }
+
+        let mut i = 0;
+        for next_p in map.get_next(&cur_p, true).iter() {
+            // Don't go back.
+            if came_from
+                .get(&cur_node)
+                .is_some_and(|prev_node| prev_node.point == *next_p)
+            {
+                continue;
+            }
+
+            let id = if i == 0 { cur_node.id } else { next_id() };
+
+            let next_node = Node::new(*next_p, id);
+            came_from.insert(next_node, cur_node);
+
+            if *next_p != map.end {
+                queue.push(next_node);
+            }
+
+            i += 1;
+        }
+    }
+
+    (0..=max_id)
+        .map(|id| reconstruct_path(&came_from, &Node::new(map.end, id)))
+        .max_by_key(|path| path.len())
+        .unwrap()
+        .iter()
+        .map(|n| n.point)
+        .collect()
+}
+
+fn reconstruct_path(came_from: &HashMap<Node, Node>, start: &Node) -> Vec<Node> {
+    let mut path = vec![*start];
+    let mut current = *start;
+
+    while let Some(n) = came_from.get(&current) {
+        current = *n;
+        path.push(*n)
+    }
+
+    path.reverse();
+
+    path
+}
+
+/// Trail map with its start in the top and end in the bottom row.
+#[derive(Debug)]
+pub struct Map {
+    pub grid: Grid<char>,
+    pub start: Point,
+    pub end: Point,
+}
+
+/// Tile of the map.
+pub type Point = Pos;
+
+impl Map {
+    /// Finds the start and end of `grid`.
+    pub fn new(grid: Grid<char>) -> Result<Self> {
+        let start = Point::new(
+            0,
+            grid.row(0)
+                .iter()
+                .position(|&c| c == '.')
+                .ok_or("no start")?,
+        );
+        let end = Point::new(
+            grid.rows() - 1,
+            grid.row(grid.rows() - 1)
+                .iter()
+                .position(|&c| c == '.')
+                .ok_or("no end")?,
+        );
+
+        Ok(Self { grid, start, end })
+    }
+
+    /// Tiles a hike can continue to from `p`, only downhill if `slopes` is set.
+    pub fn get_next(&self, p: &Point, slopes: bool) -> Vec<Point> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|d| {
+                let np = self.grid.step(*p, d)?;
+                let c = self.grid[np];
+
+                let passable = if slopes {
+                    c == '.' || c == slope(d)
+                } else {
+                    c != '#'
+                };
+
+                passable.then_some(np)
+            })
+            .collect()
+    }
+
+    fn edges(&self) -> BTreeMap<Point, BTreeSet<(Point, usize)>> {
+        let mut edges: BTreeMap<Point, BTreeSet<(Point, usize)>> = BTreeMap::new();
+
+        for (p, &ch) in self.grid.iter() {
+            if ch == '.' || ch == '>' || ch == 'v' {
+                for np in self.get_next(&p, false).iter() {
+                    edges
+                        .entry(p)
+                        .and_modify(|s| {
+                            s.insert((*np, 1));
+                        })
+                        .or_insert(BTreeSet::from_iter(vec![(*np, 1)]));
+
+                    edges
+                        .entry(*np)
+                        .and_modify(|s| {
+                            s.insert((p, 1));
+                        })
+                        .or_insert(BTreeSet::from_iter(vec![(p, 1)]));
+                }
+            }
+        }
+
+        edges
+    }
+}
+
+/// Slope which can be walked down only in the given direction.
+fn slope(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '^',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+        Direction::Right => '>',
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+struct Node {
+    id: usize,
+    point: Point,
+}
+
+impl Node {
+    fn new(point: Point, id: usize) -> Self {
+        Self { point, id }
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.id.cmp(&self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{render::Format, testing};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day23::solve1(EXAMPLE), Ok(94));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let input = Day23::parse(EXAMPLE).unwrap();
+        let canvas = Day23::visualize(&input).unwrap();
+
+        // 94 steps visit 95 tiles, including the start.
+        let ascii = canvas.render(Format::Ascii);
+        assert_eq!(ascii.matches('O').count(), 95);
+        assert!(ascii.starts_with("#O#####"));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day23::solve2(EXAMPLE), Ok(154));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day23>(30);
+    }
+
+    #[test]
+    fn test_graph() {
+        let graph = Day23::graph(&Day23::parse(EXAMPLE).unwrap()).unwrap();
+
+        // 7 crossroads between start and end.
+        assert_eq!(graph.node_count(), 9);
+        assert_eq!(graph.edge_count(), 12);
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("graph \"trails\" {\n  \"0,1\" [shape=\"doublecircle\"];\n"));
+        assert!(dot.contains("  \"0,1\" -- \"5,3\" [label=\"15\"];\n"));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day23::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::grid("#.><^v", 10)) {
+            // Start and end are the first open cells of the first and the
+            // last rows.
+            let mut lines: Vec<String> = s.lines().map(|l| ".".to_string() + &l[1..]).collect();
+            let cols = lines[0].len();
+            lines.push("#".repeat(cols - 1) + ".");
+            let text = testing::lines(&lines);
+
+            let map = Day23::parse(&text).unwrap().0;
+
+            prop_assert_eq!(map.grid.to_string(), text);
+            prop_assert_eq!(map.start, Pos::new(0, 0));
+            prop_assert_eq!(map.end, Pos::new(lines.len() - 1, cols - 1));
+        }
+    }
+}
@@ -0,0 +1,435 @@
+use common::{
+    error::{self, Context, Error, Result},
+    generate::Rng,
+    parallel,
+    solution::Solution,
+};
+use std::str::FromStr;
+
+use nalgebra::{Matrix4, Vector4};
+
+/// Positions and velocities of hailstones.
+pub struct Input(pub Vec<Hailstone>);
+
+/// Day 24: Never Tell Me The Odds.
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(read_hailstones(input)?))
+    }
+
+    /// `size` hailstones, at least 5, which a rock thrown from a random
+    /// position hits one by one, on the scale of the real input.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        generate_hail(size, rng).0
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        intersections(&input.0, (200000000000000.0, 400000000000000.0))
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        throw(&input.0)
+    }
+}
+
+fn read_hailstones(input: &str) -> Result<Vec<Hailstone>> {
+    let mut data = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let (left, right) = line
+            .split_once(" @ ")
+            .ok_or_else(|| Error::new("expected \"<position> @ <velocity>\"").at_line(i, line))?;
+        let point = left.parse::<Point>().at_line(i, line)?;
+        let veclocity = right.parse::<Veclocity>().at_line(i, line)?;
+        data.push(Hailstone::new(point, veclocity));
+    }
+
+    Ok(data)
+}
+
+/// Hailstones of [`Day24::generate`] and the position of the rock hitting
+/// them.
+fn generate_hail(size: usize, rng: &mut Rng) -> (String, [i64; 3]) {
+    let velocity = |rng: &mut Rng| -> [i64; 3] {
+        // Paths in the xy plane are lines, they can't be vertical.
+        let mut nonzero = || loop {
+            let v = rng.i64(-300..301);
+            if v != 0 {
+                return v;
+            }
+        };
+        [nonzero(), nonzero(), nonzero()]
+    };
+    let rock = [(); 3].map(|_| rng.i64(200_000_000_000_000..400_000_000_000_000));
+    let rock_velocity = velocity(rng);
+
+    let mut res = String::new();
+    for _ in 0..size.max(5) {
+        let time = rng.i64(100_000_000_000..1_000_000_000_000);
+        let v = loop {
+            let v = velocity(rng);
+            if v != rock_velocity {
+                break v;
+            }
+        };
+        let p: Vec<i64> = (0..3)
+            .map(|i| rock[i] + (rock_velocity[i] - v[i]) * time)
+            .collect();
+        res.push_str(&format!(
+            "{}, {}, {} @ {}, {}, {}\n",
+            p[0], p[1], p[2], v[0], v[1], v[2]
+        ));
+    }
+
+    (res, rock)
+}
+
+/*
+  1. (X - x) / (dx - DX) = (Y - y) / (dy - DY)
+  2. (X - x) * (dy - DY) = (Y - y) * (dx - DX)
+  3. X * dy - X * DY - x * dy + x * DY = Y * dx - Y * DX - y * dx + y * DX
+  4. Y * DX - X * DY = Y * dx - X * dy + y * DX - x * DY + x * dy - y * dx
+  5. Y * DX - X * DY = Y * dx' - X * dy' + y' * DX - x' * DY + x' * dy - y' * dx
+  6. X * (dy' - dy) + Y * (dx - dx') + DX * (y - y') + DY * (x' - x) + x * dy - x' * dy' - y * dx + y' * dx' = 0
+*/
+/// Sum of coordinates of the position from which a rock hits all hailstones.
+pub fn throw(data: &[Hailstone]) -> Result<u64> {
+    if data.len() < 5 {
+        return Err(Error::new("at least 5 hailstones are required"));
+    }
+
+    let h = data[0];
+
+    let mut coeffs1 = Vec::new();
+    let mut consts1 = Vec::new();
+    let mut coeffs2 = Vec::new();
+    let mut consts2 = Vec::new();
+
+    for &h1 in &data[1..=4] {
+        coeffs1.push(vec![
+            h1.velocity.vy - h.velocity.vy,
+            h.velocity.vx - h1.velocity.vx,
+            h.point.y - h1.point.y,
+            h1.point.x - h.point.x,
+        ]);
+        consts1.push(
+            h.point.x * h.velocity.vy - h1.point.x * h1.velocity.vy - h.point.y * h.velocity.vx
+                + h1.point.y * h1.velocity.vx,
+        );
+
+        coeffs2.push(vec![
+            h1.velocity.vz - h.velocity.vz,
+            h.velocity.vx - h1.velocity.vx,
+            h.point.z - h1.point.z,
+            h1.point.x - h.point.x,
+        ]);
+        consts2.push(
+            h.point.x * h.velocity.vz - h1.point.x * h1.velocity.vz - h.point.z * h.velocity.vx
+                + h1.point.z * h1.velocity.vx,
+        );
+    }
+
+    let s1 = solve(&coeffs1.concat(), &consts1)?;
+    let s2 = solve(&coeffs2.concat(), &consts2)?;
+
+    // Positions are too large for the solution to be exact in floats, but
+    // the small velocity is, and gives the exact position.
+    let velocity = [-s1[2], -s1[3], -s2[3]];
+    if velocity.iter().any(|v| !v.is_finite() || v.abs() > 1e12) {
+        return Err(Error::new("hailstones give a singular system"));
+    }
+    let position = rock_position(data, velocity.map(|v| v.round() as i128))?;
+
+    u64::try_from(position.iter().sum::<i128>())
+        .map_err(|_| Error::new("sum of coordinates doesn't fit u64"))
+}
+
+/// Position from which a rock thrown with `velocity` hits all hailstones,
+/// where the paths of the first hailstone and another one relative to the
+/// rock cross in the xy plane. Fails unless the rock thrown from there hits
+/// every hailstone.
+fn rock_position(data: &[Hailstone], velocity: [i128; 3]) -> Result<[i128; 3]> {
+    let relative = |h: &Hailstone| {
+        (
+            [h.point.x, h.point.y, h.point.z].map(|c| c as i128),
+            [
+                h.velocity.vx as i128 - velocity[0],
+                h.velocity.vy as i128 - velocity[1],
+                h.velocity.vz as i128 - velocity[2],
+            ],
+        )
+    };
+    let (p, v) = relative(data.first().ok_or("no hailstones")?);
+
+    let position = data[1..]
+        .iter()
+        .find_map(|h| {
+            let (q, w) = relative(h);
+            let denominator = v[0] * w[1] - v[1] * w[0];
+            if denominator == 0 {
+                return None;
+            }
+            let numerator = (q[0] - p[0]) * w[1] - (q[1] - p[1]) * w[0];
+            if numerator % denominator != 0 {
+                return None;
+            }
+            let time = numerator / denominator;
+            Some([0, 1, 2].map(|i| p[i] + v[i] * time))
+        })
+        .ok_or_else(|| Error::new("hailstones give a singular system"))?;
+
+    for (i, h) in data.iter().enumerate() {
+        // Hit at a time when the hailstone is at the rock's position relative
+        // to it.
+        let (q, w) = relative(h);
+        let axis = (0..3).find(|&i| w[i] != 0);
+        let hit = match axis {
+            Some(axis) => {
+                let offset = position[axis] - q[axis];
+                offset % w[axis] == 0 && {
+                    let time = offset / w[axis];
+                    time >= 0 && (0..3).all(|i| q[i] + w[i] * time == position[i])
+                }
+            }
+            None => q == position,
+        };
+        if !hit {
+            return Err(Error::new(format!(
+                "rock thrown from {:?} with velocity {:?} misses hailstone {}",
+                position,
+                velocity,
+                i + 1
+            )));
+        }
+    }
+
+    Ok(position)
+}
+
+fn solve(coeffs: &[f64], consts: &[f64]) -> Result<Vec<f64>> {
+    let matrix = Matrix4::from_row_slice(coeffs);
+    let rhs = Vector4::from_column_slice(consts);
+
+    let inverse = matrix
+        .try_inverse()
+        .ok_or_else(|| Error::new("hailstones give a singular system"))?;
+
+    Ok((inverse * rhs)
+        .iter()
+        .map(|&v| v.round())
+        .collect::<Vec<f64>>())
+}
+
+/// Number of future path intersections inside the `(min, max)` test area.
+pub fn intersections(data: &[Hailstone], (min, max): (f64, f64)) -> Result<u64> {
+    // Each hailstone counts intersections with itself and the ones after it.
+    let counts = parallel::map_range(0..data.len(), |i| {
+        let a = data[i];
+        data[i..]
+            .iter()
+            .filter(|b| {
+                a.line().intersection(&b.line()).is_some_and(|p| {
+                    a.is_future(p)
+                        && b.is_future(p)
+                        && p.0 >= min
+                        && p.0 <= max
+                        && p.1 >= min
+                        && p.1 <= max
+                })
+            })
+            .count() as u64
+    });
+
+    Ok(counts.into_iter().sum())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Line {
+    a: f64,
+    b: f64,
+}
+
+impl Line {
+    fn intersection(&self, other: &Line) -> Option<(f64, f64)> {
+        if self.a == other.a {
+            return None;
+        }
+        let x = (self.b - other.b) / (other.a - self.a);
+        let y = self.a * x + self.b;
+        Some((x, y))
+    }
+}
+
+/// Hailstone with its position and velocity.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Hailstone {
+    pub point: Point,
+    pub velocity: Veclocity,
+}
+
+impl Hailstone {
+    pub fn new(point: Point, velocity: Veclocity) -> Self {
+        Self { point, velocity }
+    }
+
+    fn line(&self) -> Line {
+        assert!(self.velocity.vx != 0.0 && self.velocity.vy != 0.0);
+        let a: f64 = self.velocity.vy / self.velocity.vx;
+        let b = self.point.y - self.point.x * a;
+        Line { a, b }
+    }
+
+    fn is_future(&self, p: (f64, f64)) -> bool {
+        ((self.velocity.vx > 0.0 && p.0 > self.point.x)
+            || (self.velocity.vx < 0.0 && p.0 < self.point.x))
+            && ((self.velocity.vy > 0.0 && p.1 > self.point.y)
+                || (self.velocity.vy < 0.0 && p.1 < self.point.y))
+    }
+}
+
+/// Position in space.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl FromStr for Point {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [x, y, z] = coordinates(s)?;
+        Ok(Self { x, y, z })
+    }
+}
+
+/// Velocity per nanosecond.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Veclocity {
+    pub vx: f64,
+    pub vy: f64,
+    pub vz: f64,
+}
+
+impl FromStr for Veclocity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [vx, vy, vz] = coordinates(s)?;
+        Ok(Self { vx, vy, vz })
+    }
+}
+
+/// Parses three comma separated integer coordinates of `s`. Integers keep
+/// out values like `NaN` which solvers can't handle.
+fn coordinates(s: &str) -> Result<[f64; 3]> {
+    let parts: Vec<&str> = s.split(',').collect();
+    if parts.len() != 3 {
+        return Err(Error::new("expected 3 coordinates").with_text(s));
+    }
+
+    let mut res = [0.0; 3];
+    for (value, part) in res.iter_mut().zip(parts) {
+        *value = error::parse::<i64>(part.trim(), "coordinate")? as f64;
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        // Test area of the example is smaller.
+        let hailstones = read_hailstones(EXAMPLE).unwrap();
+        assert_eq!(intersections(&hailstones, (7.0, 27.0)), Ok(2));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day24::solve2(EXAMPLE), Ok(47));
+
+        // The rock through the first five hailstones misses the last one.
+        let input = format!("{}1, 2, 3 @ 1, 1, 1\n", EXAMPLE);
+        assert_eq!(
+            Day24::solve2(&input).unwrap_err().message(),
+            "rock thrown from [24, 13, 10] with velocity [-3, 1, 2] misses hailstone 6"
+        );
+    }
+
+    #[test]
+    fn test_part2_exact() {
+        // Rock at 24, 13, 10 with velocity -3, 1, 2 and coordinates too large
+        // for floats to hit exactly.
+        let offset = 300_000_000_000_001_i64;
+        let lines: Vec<String> = EXAMPLE
+            .lines()
+            .map(|line| {
+                let (p, v) = line.split_once(" @ ").unwrap();
+                let p: Vec<String> = p
+                    .split(", ")
+                    .map(|c| (c.trim().parse::<i64>().unwrap() + offset).to_string())
+                    .collect();
+                format!("{} @ {}", p.join(", "), v)
+            })
+            .collect();
+
+        assert_eq!(
+            Day24::solve2(&testing::lines(&lines)),
+            Ok(47 + 3 * offset as u64)
+        );
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day24>(300);
+
+        for seed in 0..20 {
+            let (text, rock) = generate_hail(300, &mut common::generate::Rng::new(seed));
+            assert_eq!(
+                Day24::solve2(&text),
+                Ok(rock.iter().sum::<i64>() as u64),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day24::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(hailstones in prop::collection::vec((any::<[i32; 3]>(), any::<[i16; 3]>()), 0..5)) {
+            let lines: Vec<String> = hailstones
+                .iter()
+                .map(|([x, y, z], [vx, vy, vz])| format!("{}, {}, {} @ {:>3}, {:>3}, {:>3}", x, y, z, vx, vy, vz))
+                .collect();
+
+            let input = Day24::parse(&testing::lines(&lines)).unwrap();
+
+            prop_assert_eq!(input.0.len(), hailstones.len());
+            for (h, ([x, y, z], [vx, vy, vz])) in input.0.iter().zip(&hailstones) {
+                prop_assert_eq!(h.point, Point { x: *x as f64, y: *y as f64, z: *z as f64 });
+                prop_assert_eq!(h.velocity, Veclocity { vx: *vx as f64, vy: *vy as f64, vz: *vz as f64 });
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use common::{
+    error::{Error, Result},
+    generate::Rng,
+    graph::Graph,
+    solution::{NoAnswer, Solution},
+};
+use std::collections::{BTreeSet, HashMap};
+
+use nalgebra::{DMatrix, RealField};
+
+/// Components and wires between them.
+pub struct Input {
+    pub nodes: BTreeSet<String>,
+    pub edges: HashMap<String, BTreeSet<String>>,
+}
+
+/// Day 25: Snowverload.
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const PARTS: u8 = 1;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = NoAnswer;
+
+    fn parse(input: &str) -> Result<Input> {
+        let mut edges: HashMap<String, BTreeSet<String>> = HashMap::new();
+        let mut nodes: BTreeSet<String> = BTreeSet::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let (node, adj) = line
+                .split_once(": ")
+                .ok_or_else(|| Error::new("expected \"<node>: <nodes>\"").at_line(i, line))?;
+            nodes.insert(node.to_owned());
+
+            for n in adj.split(" ") {
+                nodes.insert(n.to_owned());
+                edges
+                    .entry(node.to_owned())
+                    .and_modify(|s| {
+                        if n != node {
+                            s.insert(n.to_owned());
+                        }
+                    })
+                    .or_insert(if n != node {
+                        BTreeSet::from_iter(vec![n.to_owned()])
+                    } else {
+                        BTreeSet::new()
+                    });
+                edges
+                    .entry(n.to_owned())
+                    .and_modify(|s| {
+                        if n != node {
+                            s.insert(node.to_owned());
+                        }
+                    })
+                    .or_insert(if n != node {
+                        BTreeSet::from_iter(vec![node.to_owned()])
+                    } else {
+                        BTreeSet::new()
+                    });
+            }
+        }
+
+        Ok(Input { nodes, edges })
+    }
+
+    /// `size` components in two groups, each component is wired to up to
+    /// four others of its group and exactly three wires join the groups.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let count = size.clamp(10, 26 * 26 * 26);
+        let mut ids: Vec<usize> = (0..26 * 26 * 26).collect();
+        rng.shuffle(&mut ids);
+        let names: Vec<String> = ids[..count]
+            .iter()
+            .map(|&i| {
+                [i / 676, i / 26 % 26, i % 26]
+                    .iter()
+                    .map(|&l| (b'a' + l as u8) as char)
+                    .collect()
+            })
+            .collect();
+
+        // Components before `split` are the first group.
+        let split = rng.usize(count / 3..count - count / 3);
+        let mut wires: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); count];
+        for (i, wired) in wires.iter_mut().enumerate() {
+            let first = if i < split { 0 } else { split };
+            for _ in 0..(i - first).min(4) {
+                wired.insert(rng.usize(first..i));
+            }
+        }
+        let mut left: Vec<usize> = (0..split).collect();
+        let mut right: Vec<usize> = (split..count).collect();
+        rng.shuffle(&mut left);
+        rng.shuffle(&mut right);
+        for (&a, &b) in left.iter().zip(&right).take(3) {
+            wires[b].insert(a);
+        }
+
+        wires
+            .iter()
+            .enumerate()
+            .filter(|(_, wired)| !wired.is_empty())
+            .map(|(i, wired)| {
+                let wired: Vec<&str> = wired.iter().map(|&j| names[j].as_str()).collect();
+                format!("{}: {}\n", names[i], wired.join(" "))
+            })
+            .collect()
+    }
+
+    fn part1(input: &Input) -> Result<usize> {
+        let Input { nodes, edges } = input;
+
+        // https://patterns.eecs.berkeley.edu/?page_id=571#4_Spectral_Bisection
+        // https://github.com/alexcani/adventofcode2023/blob/master/src/bin/25.rs#L62
+        let mut matrix: DMatrix<f32> = DMatrix::<f32>::zeros(nodes.len(), nodes.len());
+
+        for (i, n) in nodes.iter().enumerate() {
+            matrix[(i, i)] = edges.get(n).unwrap().len() as f32;
+            for e in edges.get(n).unwrap() {
+                let j = nodes.iter().enumerate().find(|&(_, it)| it == e).unwrap().0;
+                matrix[(i, j)] = -1.0
+            }
+        }
+
+        let eigen = matrix.clone().symmetric_eigen();
+
+        let mut min_value = f32::MAX;
+        let mut min_index = 0;
+        let mut second_min_value = f32::MAX;
+        let mut second_min_index = 0;
+
+        for (i, &v) in eigen.eigenvalues.iter().enumerate() {
+            if v < min_value {
+                second_min_value = min_value;
+                second_min_index = min_index;
+                min_value = v;
+                min_index = i;
+            } else if v < second_min_value {
+                second_min_value = v;
+                second_min_index = i;
+            }
+        }
+
+        let vector = eigen.eigenvectors.column(second_min_index);
+
+        let mut pos = 0;
+        let mut neg = 0;
+
+        for v in vector.iter() {
+            if v.is_sign_positive() {
+                pos += 1;
+            } else {
+                neg += 1;
+            }
+        }
+
+        Ok(pos * neg)
+    }
+
+    fn part2(_: &Input) -> Result<NoAnswer> {
+        Err(Error::new("the last day has a single part"))
+    }
+
+    fn graph(input: &Input) -> Result<Graph> {
+        Ok(graph(input))
+    }
+}
+
+/// Components with an undirected edge for each wire.
+pub fn graph(input: &Input) -> Graph {
+    let mut res = Graph::undirected("components");
+    for node in &input.nodes {
+        res.node(node, &[]);
+        // Each wire is stored at both of its ends.
+        for other in input.edges[node].iter().filter(|&other| node < other) {
+            res.edge(node, other, &[]);
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day25::solve1(EXAMPLE), Ok(54));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day25>(100);
+    }
+
+    #[test]
+    fn test_graph() {
+        let graph = Day25::graph(&Day25::parse(EXAMPLE).unwrap()).unwrap();
+
+        assert_eq!(graph.node_count(), 15);
+        assert_eq!(graph.edge_count(), 33);
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("graph \"components\" {\n  \"bvb\";\n  \"cmg\";\n"));
+        assert!(dot.contains("  \"bvb\" -- \"cmg\";\n"));
+        assert!(!dot.contains("  \"cmg\" -- \"bvb\";\n"));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day25::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            wires in prop::collection::btree_map("[a-e]{3}", prop::collection::vec("[a-e]{3}", 1..4), 1..10)
+        ) {
+            let lines: Vec<String> = wires.iter().map(|(node, adj)| format!("{}: {}", node, adj.join(" "))).collect();
+
+            let input = Day25::parse(&testing::lines(&lines)).unwrap();
+
+            let mut nodes = BTreeSet::new();
+            let mut edges: HashMap<String, BTreeSet<String>> = HashMap::new();
+            for (node, adj) in &wires {
+                for n in adj {
+                    nodes.extend([node.clone(), n.clone()]);
+                    edges.entry(node.clone()).or_default();
+                    edges.entry(n.clone()).or_default();
+                    if n != node {
+                        edges.get_mut(node).unwrap().insert(n.clone());
+                        edges.get_mut(n).unwrap().insert(node.clone());
+                    }
+                }
+            }
+
+            prop_assert_eq!(input.nodes, nodes);
+            prop_assert_eq!(input.edges, edges);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use common::{
+    error::{self, Context, Result},
+    generate::Rng,
+    solution::Solution,
+};
+
+/// Engine schematic, numbers and symbols are found while solving.
+pub struct Input(pub String);
+
+/// Day 3: Gear Ratios.
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(input.to_string()))
+    }
+
+    /// Schematic of `size` x `size` with numbers up to 999 and symbols
+    /// scattered over it.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '=', '%', '@', '&', '-'];
+
+        let mut res = String::new();
+        for _ in 0..size {
+            let mut row = String::new();
+            while row.len() < size {
+                let digits = rng.usize(1..4);
+                if rng.chance(0.15) && row.len() + digits <= size {
+                    row += &rng
+                        .usize(10usize.pow(digits as u32 - 1)..10usize.pow(digits as u32))
+                        .to_string();
+                    if row.len() < size {
+                        row.push('.');
+                    }
+                } else if rng.chance(0.06) {
+                    row.push(*rng.pick(&SYMBOLS));
+                } else {
+                    row.push('.');
+                }
+            }
+            res += &row;
+            res.push('\n');
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u32> {
+        calc(
+            &input.0,
+            |n| if n.has_adjacent_sym { n.value } else { 0 },
+            |_| 0,
+        )
+    }
+
+    fn part2(input: &Input) -> Result<u32> {
+        calc(
+            &input.0,
+            |_| 0,
+            |s| {
+                if s.adjacent_nums_count == 2 {
+                    s.first_two_adjacent_nums.0.unwrap().value
+                        * s.first_two_adjacent_nums.1.unwrap().value
+                } else {
+                    0
+                }
+            },
+        )
+    }
+}
+
+/// Scans schematic and sums `num_fn` of all numbers and `sym_fn` of all
+/// symbols, with adjacency of numbers and symbols already resolved.
+pub fn calc(input: &str, num_fn: fn(&Num) -> u32, sym_fn: fn(&Sym) -> u32) -> Result<u32> {
+    let mut lines = input.lines().enumerate().peekable();
+    let mut num_pos: Option<Pos>;
+    let mut sum: u32 = 0;
+    let mut nums: Vec<Num> = Vec::new();
+    let mut syms: Vec<Sym> = Vec::new();
+
+    while let Some((i, line)) = lines.next() {
+        num_pos = None;
+
+        for (j, c) in line.chars().enumerate() {
+            match c {
+                '0'..='9' => match num_pos {
+                    Some(ref mut pos) => pos.end = j + 1,
+                    None => num_pos = Some(Pos::new(i, j, j + 1)),
+                },
+                _ => {
+                    match c {
+                        '.' => {}
+                        _ => syms.push(Sym::new(i, j)),
+                    }
+                    parse_and_save(line, &num_pos, &mut nums).at_line(i, line)?;
+                    num_pos = None
+                }
+            }
+        }
+        parse_and_save(line, &num_pos, &mut nums).at_line(i, line)?;
+
+        if i >= 2 {
+            for num in nums.iter_mut() {
+                for sym in syms.iter_mut() {
+                    if is_adjacent(sym, num) {
+                        match sym.first_two_adjacent_nums {
+                            (None, None) => {
+                                sym.first_two_adjacent_nums = (Some(*num), None);
+                                sym.adjacent_nums_count += 1;
+                            }
+                            (Some(n1), None) => {
+                                if num.pos != n1.pos {
+                                    sym.first_two_adjacent_nums = (Some(n1), Some(*num));
+                                    sym.adjacent_nums_count += 1;
+                                }
+                            }
+                            (Some(n1), Some(n2)) => {
+                                if num.pos != n1.pos && num.pos != n2.pos {
+                                    sym.adjacent_nums_count += 1;
+                                }
+                            }
+                            _ => panic!("must never happen"),
+                        }
+                        num.has_adjacent_sym = true;
+                    }
+                }
+            }
+
+            for num in nums
+                .iter()
+                .filter(|n| n.pos.row == (i - 2) || lines.peek().is_none())
+            {
+                sum += num_fn(num)
+            }
+            nums.retain(|n| n.pos.row > (i - 2));
+
+            for sym in syms
+                .iter()
+                .filter(|s| s.pos.row == (i - 2) || lines.peek().is_none())
+            {
+                sum += sym_fn(sym)
+            }
+            syms.retain(|s| s.pos.row > (i - 2));
+        }
+    }
+
+    Ok(sum)
+}
+
+fn parse_and_save(line: &str, pos: &Option<Pos>, nums: &mut Vec<Num>) -> Result<()> {
+    if let Some(pos) = pos {
+        let s = &line[pos.start..pos.end];
+        let n: u32 = error::parse(s, "number")?;
+        nums.push(Num::new(*pos, n));
+    }
+    Ok(())
+}
+
+/// Whether the number touches the symbol, diagonally too.
+pub fn is_adjacent(sym: &Sym, num: &Num) -> bool {
+    sym.pos.row + 1 >= num.pos.row
+        && sym.pos.row <= num.pos.row + 1
+        && sym.pos.start + 1 >= num.pos.start
+        && sym.pos.start <= num.pos.end
+}
+
+/// Row and columns range `start..end` in the schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pos {
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Pos {
+    fn new(row: usize, start: usize, end: usize) -> Pos {
+        Pos { row, start, end }
+    }
+}
+
+/// Part number with its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Num {
+    pub pos: Pos,
+    pub value: u32,
+    pub has_adjacent_sym: bool,
+}
+
+impl Num {
+    fn new(pos: Pos, value: u32) -> Num {
+        Num {
+            pos,
+            value,
+            has_adjacent_sym: false,
+        }
+    }
+}
+
+/// Symbol with numbers adjacent to it.
+#[derive(Debug, Clone, Copy)]
+pub struct Sym {
+    pub pos: Pos,
+    pub adjacent_nums_count: u32,
+    pub first_two_adjacent_nums: (Option<Num>, Option<Num>),
+}
+
+impl Sym {
+    fn new(row: usize, col: usize) -> Sym {
+        Sym {
+            pos: Pos::new(row, col, col),
+            adjacent_nums_count: 0,
+            first_two_adjacent_nums: (None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day3::solve1(EXAMPLE), Ok(4361));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day3::solve2(EXAMPLE), Ok(467835));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day3>(140);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day3::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(s in testing::text()) {
+            prop_assert_eq!(Day3::parse(&s).unwrap().0, s);
+        }
+    }
+}
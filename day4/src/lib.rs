@@ -0,0 +1,257 @@
+use std::{collections::VecDeque, io::BufRead};
+
+use common::{
+    error::{self, Error, Result},
+    generate::Rng,
+    solution::Solution,
+    stream::{self, Streaming},
+};
+
+/// Scratchcards in the order of the puzzle input.
+pub struct Input(pub Vec<Card>);
+
+/// Day 4: Scratchcards.
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(
+            stream::lines::<Self, _>(input.as_bytes()).collect::<Result<_>>()?,
+        ))
+    }
+
+    /// `size` cards with 10 winning numbers and 25 numbers you have. Most
+    /// cards have no matches, so the number of copies stays bounded.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut res = String::new();
+        for card in 1..=size {
+            let mut pool: Vec<u32> = (1..100).collect();
+            rng.shuffle(&mut pool);
+            let matches = if rng.chance(0.6) { 0 } else { rng.usize(1..4) };
+
+            let left = &pool[..10];
+            let mut right: Vec<u32> = left[..matches].to_vec();
+            right.extend(&pool[10..35 - matches]);
+            rng.shuffle(&mut right);
+
+            let numbers = |ns: &[u32]| {
+                let ns: Vec<String> = ns.iter().map(|n| format!("{:>2}", n)).collect();
+                ns.join(" ")
+            };
+            res += &format!(
+                "Card {:>3}: {} | {}\n",
+                card,
+                numbers(left),
+                numbers(&right)
+            );
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        Ok(Pile::of(&input.0).points)
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        Ok(Pile::of(&input.0).cards)
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Result<(u64, u64)> {
+        stream::solve::<Self>(reader)
+    }
+}
+
+impl Streaming for Day4 {
+    type Line = Card;
+    type State = Pile;
+
+    fn parse_line(line: &str) -> Result<Card> {
+        Card::from_string(line)
+    }
+
+    fn push(pile: &mut Pile, card: &Card) -> Result<()> {
+        pile.add(card);
+        Ok(())
+    }
+
+    fn answers(pile: &Pile) -> (u64, u64) {
+        (pile.points, pile.cards)
+    }
+}
+
+/// Points and number of cards with their copies of the cards seen so far.
+#[derive(Debug, Default)]
+pub struct Pile {
+    pub points: u64,
+    pub cards: u64,
+    /// Copies won of the following cards, starting with the next one. Never
+    /// longer than the most matches of a card.
+    won: VecDeque<u64>,
+}
+
+impl Pile {
+    pub fn of(cards: &[Card]) -> Self {
+        let mut pile = Pile::default();
+        for card in cards {
+            pile.add(card);
+        }
+        pile
+    }
+
+    /// Adds the next card with its copies won by the previous cards.
+    pub fn add(&mut self, card: &Card) {
+        let matches = card.matches() as usize;
+        if matches > 0 {
+            self.points += 1 << (matches - 1);
+        }
+
+        let copies = 1 + self.won.pop_front().unwrap_or(0);
+        self.cards += copies;
+
+        for i in 0..matches {
+            match self.won.get_mut(i) {
+                Some(won) => *won += copies,
+                None => self.won.push_back(copies),
+            }
+        }
+    }
+}
+
+/// Card with winning numbers on the left and numbers you have on the right,
+/// both sorted.
+#[derive(Debug, Clone)]
+pub struct Card {
+    pub index: u32,
+    pub left: Vec<u32>,
+    pub right: Vec<u32>,
+}
+
+impl Card {
+    /// Parses "Card N: <numbers> | <numbers>" line.
+    pub fn from_string(s: &str) -> Result<Card> {
+        let (card_info_str, numbers_str) = s
+            .split_once(": ")
+            .ok_or_else(|| Error::new("expected \"Card N: ...\"").with_text(s))?;
+        let card_index_str = card_info_str
+            .split_whitespace()
+            .last()
+            .ok_or_else(|| Error::invalid("card", card_info_str))?;
+        let (left_str, right_str) = numbers_str
+            .split_once(" | ")
+            .ok_or_else(|| Error::new("expected \" | \" separator").with_text(numbers_str))?;
+
+        Ok(Card {
+            index: error::parse(card_index_str, "card number")?,
+            left: parse_numbers(left_str)?,
+            right: parse_numbers(right_str)?,
+        })
+    }
+
+    /// Count of numbers you have which are winning.
+    pub fn matches(&self) -> u32 {
+        let mut li: usize = 0;
+        let mut ri: usize = 0;
+        let mut matches: u32 = 0;
+
+        while li < self.left.len() && ri < self.right.len() {
+            let lv = self.left[li];
+            let rv = self.right[ri];
+
+            if lv == rv {
+                matches += 1;
+                li += 1;
+                ri += 1;
+            } else if lv < rv {
+                li += 1
+            } else if lv > rv {
+                ri += 1
+            }
+        }
+
+        matches
+    }
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<u32>> {
+    let mut nums = Vec::new();
+
+    for num_str in s.split_whitespace() {
+        let num = error::parse(num_str, "number")?;
+        nums.push(num)
+    }
+    nums.sort();
+
+    Ok(nums)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day4::solve1(EXAMPLE), Ok(13));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day4::solve2(EXAMPLE), Ok(30));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day4>(2000);
+    }
+
+    #[test]
+    fn test_stream() {
+        testing::stream::<Day4>(2000);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day4::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            cards in prop::collection::vec(
+                (prop::collection::vec(1..100_u32, 1..10), prop::collection::vec(1..100_u32, 1..10)),
+                0..5,
+            )
+        ) {
+            let lines: Vec<String> = cards
+                .iter()
+                .enumerate()
+                .map(|(i, (left, right))| {
+                    let nums = |v: &Vec<u32>| v.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ");
+                    format!("Card {:>3}: {} | {}", i + 1, nums(left), nums(right))
+                })
+                .collect();
+
+            let input = Day4::parse(&testing::lines(&lines)).unwrap();
+
+            prop_assert_eq!(input.0.len(), cards.len());
+            for (i, (card, (left, right))) in input.0.iter().zip(&cards).enumerate() {
+                let (mut left, mut right) = (left.clone(), right.clone());
+                left.sort();
+                right.sort();
+
+                prop_assert_eq!(card.index as usize, i + 1);
+                prop_assert_eq!(&card.left, &left);
+                prop_assert_eq!(&card.right, &right);
+            }
+        }
+    }
+}
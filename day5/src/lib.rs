@@ -0,0 +1,395 @@
+use common::{
+    check::Check,
+    error::{self, Context, Error, Result},
+    generate::Rng,
+    parallel,
+    solution::Solution,
+};
+use itertools::Itertools;
+
+/// Seeds and the chain of maps from seed to location.
+pub struct Input {
+    pub seeds: Vec<u64>,
+    pub seed_ranges: Vec<(u64, u64)>,
+    pub maps: Vec<Map>,
+}
+
+/// Day 5: If You Give A Seed A Fertilizer.
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        let mut seeds: Vec<u64> = Vec::new();
+        let mut seed_ranges: Vec<(u64, u64)> = Vec::new();
+        let mut maps: Vec<Map> = Vec::new();
+        let mut map: Option<Map> = None;
+
+        for (i, line) in input.lines().enumerate() {
+            if let Some(s) = line.strip_prefix("seeds: ") {
+                seeds = parse_seeds(s).at_line(i, line)?;
+                seed_ranges = parse_seed_ranges(s).at_line(i, line)?;
+            } else if let Some(s) = line.strip_suffix(" map:") {
+                map = Some(parse_map(s).at_line(i, line)?);
+            } else if line.is_empty() {
+                if let Some(map) = map {
+                    maps.push(map);
+                }
+                map = None
+            } else {
+                if let Some(ref mut map) = map {
+                    map.ranges.push(parse_range(line).at_line(i, line)?);
+                }
+            }
+        }
+        if let Some(map) = map {
+            maps.push(map);
+        }
+
+        Ok(Input {
+            seeds,
+            seed_ranges,
+            maps,
+        })
+    }
+
+    /// 10 seed ranges of up to `10 * size` seeds and the 7 maps of the
+    /// puzzle, each of `size` ranges shuffling numbers below `1000 * size`.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const CATEGORIES: [&str; 8] = [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location",
+        ];
+        let size = size.max(1);
+        let limit = 1000 * size as u64;
+
+        let seeds: Vec<String> = (0..10)
+            .map(|_| {
+                let count = rng.below(10 * size as u64) + 1;
+                let start = rng.below(limit - count + 1);
+                format!("{} {}", start, count)
+            })
+            .collect();
+        let mut res = format!("seeds: {}\n", seeds.join(" "));
+
+        for pair in CATEGORIES.windows(2) {
+            // Source segments in order are laid out in a random order as
+            // destinations, so the map is a permutation of numbers below the
+            // limit.
+            let mut cuts: Vec<u64> = (1..size).map(|_| rng.below(limit - 1) + 1).collect();
+            cuts.extend([0, limit]);
+            cuts.sort();
+            cuts.dedup();
+            let mut segments: Vec<(u64, u64)> =
+                cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+            rng.shuffle(&mut segments);
+
+            res += &format!("\n{}-to-{} map:\n", pair[0], pair[1]);
+            let mut destination = 0;
+            for (source, count) in segments {
+                res += &format!("{} {} {}\n", destination, source, count);
+                destination += count;
+            }
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        let mut min: u64 = u64::MAX;
+
+        for seed in input.seeds.iter() {
+            let mut res: u64 = *seed;
+            for map in input.maps.iter() {
+                res = map.apply(res)
+            }
+            if res < min {
+                min = res
+            }
+        }
+
+        Ok(min)
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        lowest_location(&input.maps, &input.seed_ranges).ok_or_else(|| Error::new("no seeds"))
+    }
+
+    /// Mapping seeds one by one against mapping whole ranges of them.
+    fn cross_check(input: &Input) -> Result<Vec<Check>> {
+        let seeds: Vec<(u64, u64)> = input.seeds.iter().map(|&seed| (seed, 1)).collect();
+        let ranges = lowest_location(&input.maps, &seeds).ok_or_else(|| Error::new("no seeds"))?;
+
+        Ok(vec![
+            Check::new(1, "per seed vs ranges", Self::part1(input)?, ranges),
+            Check::new(
+                2,
+                "per seed vs ranges",
+                brute_force(input)?,
+                Self::part2(input)?,
+            ),
+        ])
+    }
+}
+
+/// Lowest location of seeds in `ranges` of start and count, by mapping
+/// ranges split where ranges of maps begin and end.
+pub fn lowest_location(maps: &[Map], ranges: &[(u64, u64)]) -> Option<u64> {
+    let mut ranges: Vec<(u64, u64)> = ranges.iter().copied().filter(|r| r.1 > 0).collect();
+
+    for map in maps {
+        ranges = ranges
+            .into_iter()
+            .flat_map(|r| map.apply_range(r))
+            .collect();
+    }
+
+    ranges.into_iter().map(|(start, _)| start).min()
+}
+
+/// Lowest location of seeds in the seed ranges by mapping each seed, in
+/// parallel chunks of seeds. Slow on the real input.
+pub fn brute_force(input: &Input) -> Result<u64> {
+    // Seed ranges are of very different sizes, smaller chunks keep all threads
+    // busy.
+    const CHUNK: u64 = 100_000;
+    let chunks: Vec<(u64, u64)> = input
+        .seed_ranges
+        .iter()
+        .flat_map(|&(start, count)| {
+            (start..start + count)
+                .step_by(CHUNK as usize)
+                .map(move |from| (from, CHUNK.min(start + count - from)))
+        })
+        .collect();
+
+    parallel::map(&chunks, |&(start, count)| {
+        (start..start + count)
+            .map(|seed| input.maps.iter().fold(seed, |n, map| map.apply(n)))
+            .min()
+    })
+    .into_iter()
+    .flatten()
+    .min()
+    .ok_or_else(|| Error::new("no seeds"))
+}
+
+fn parse_seeds(s: &str) -> Result<Vec<u64>> {
+    let mut nums: Vec<u64> = Vec::new();
+    for ns in s.split_whitespace() {
+        nums.push(error::parse(ns, "seed")?);
+    }
+    Ok(nums)
+}
+
+fn parse_seed_ranges(s: &str) -> Result<Vec<(u64, u64)>> {
+    let mut res: Vec<(u64, u64)> = Vec::new();
+
+    for (start, count) in s.split_whitespace().tuples() {
+        res.push((
+            error::parse(start, "seed")?,
+            error::parse(count, "seed count")?,
+        ));
+    }
+    Ok(res)
+}
+
+fn parse_map(s: &str) -> Result<Map> {
+    let (from, to) = s
+        .split_once("-to-")
+        .ok_or_else(|| Error::invalid("map name", s))?;
+    Ok(Map::new(from, to))
+}
+
+fn parse_range(s: &str) -> Result<Range> {
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    let [dst, src, count] = parts[..] else {
+        return Err(Error::new("expected \"<destination> <source> <count>\"").with_text(s));
+    };
+
+    Ok(Range {
+        source: error::parse(src, "source")?,
+        destination: error::parse(dst, "destination")?,
+        count: error::parse(count, "count")?,
+    })
+}
+
+/// Map from one category to another, numbers outside of all ranges map to
+/// themselves.
+#[derive(Debug)]
+pub struct Map {
+    pub from: String,
+    pub to: String,
+    pub ranges: Vec<Range>,
+}
+
+impl Map {
+    pub fn new(from: &str, to: &str) -> Map {
+        Map {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            ranges: Vec::new(),
+        }
+    }
+
+    /// Maps source number to the destination one.
+    pub fn apply(&self, src: u64) -> u64 {
+        for r in self.ranges.iter() {
+            if r.contains(src) {
+                return r.apply(src);
+            }
+        }
+        src
+    }
+
+    /// Maps `count` source numbers from `start` to the ranges of destination
+    /// numbers they cover, as start and count.
+    pub fn apply_range(&self, (start, count): (u64, u64)) -> Vec<(u64, u64)> {
+        let mut res = Vec::new();
+        // Parts not mapped by any range yet, as start and end.
+        let mut rest = vec![(start, start + count)];
+
+        for r in &self.ranges {
+            let mut next = Vec::new();
+            for (start, end) in rest {
+                let from = start.max(r.source);
+                let to = end.min(r.source + r.count);
+                if from < to {
+                    res.push((r.apply(from), to - from));
+                    next.extend(
+                        [(start, from), (to, end)]
+                            .into_iter()
+                            .filter(|(s, e)| s < e),
+                    );
+                } else {
+                    next.push((start, end));
+                }
+            }
+            rest = next;
+        }
+        res.extend(rest.into_iter().map(|(start, end)| (start, end - start)));
+
+        res
+    }
+}
+
+/// Range of `count` source numbers mapped to destination ones.
+#[derive(Debug)]
+pub struct Range {
+    pub source: u64,
+    pub destination: u64,
+    pub count: u64,
+}
+
+impl Range {
+    pub fn contains(&self, src: u64) -> bool {
+        src >= self.source && src < self.source + self.count
+    }
+
+    pub fn apply(&self, src: u64) -> u64 {
+        self.destination + (src - self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day5::solve1(EXAMPLE), Ok(35));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day5::solve2(EXAMPLE), Ok(46));
+    }
+
+    #[test]
+    fn test_apply_range() {
+        let mut map = Map::new("seed", "soil");
+        map.ranges.push(parse_range("50 98 2").unwrap());
+        map.ranges.push(parse_range("52 50 48").unwrap());
+
+        let mut ranges = map.apply_range((40, 70));
+        ranges.sort();
+        assert_eq!(ranges, vec![(40, 10), (50, 2), (52, 48), (100, 10)]);
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let checks = Day5::cross_check(&Day5::parse(EXAMPLE).unwrap()).unwrap();
+        assert_eq!(
+            checks,
+            vec![
+                Check::new(1, "per seed vs ranges", 35, 35),
+                Check::new(2, "per seed vs ranges", 46, 46),
+            ]
+        );
+
+        testing::cross_check::<Day5>(50);
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day5>(50);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day5::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            seeds in prop::collection::vec((any::<u32>(), any::<u32>()), 1..5),
+            maps in prop::collection::vec(prop::collection::vec((any::<u32>(), any::<u32>(), any::<u32>()), 1..4), 0..4),
+        ) {
+            let mut text = "seeds:".to_string();
+            for (start, count) in &seeds {
+                text += &format!(" {} {}", start, count);
+            }
+            text += "\n";
+            for (i, ranges) in maps.iter().enumerate() {
+                text += &format!("\nm{}-to-m{} map:\n", i, i + 1);
+                for (dst, src, count) in ranges {
+                    text += &format!("{} {} {}\n", dst, src, count);
+                }
+            }
+
+            let input = Day5::parse(&text).unwrap();
+
+            let flat: Vec<u64> = seeds.iter().flat_map(|&(s, c)| [s as u64, c as u64]).collect();
+            prop_assert_eq!(&input.seeds, &flat);
+            let pairs: Vec<(u64, u64)> = seeds.iter().map(|&(s, c)| (s as u64, c as u64)).collect();
+            prop_assert_eq!(&input.seed_ranges, &pairs);
+
+            prop_assert_eq!(input.maps.len(), maps.len());
+            for (i, (map, ranges)) in input.maps.iter().zip(&maps).enumerate() {
+                prop_assert_eq!(&map.from, &format!("m{}", i));
+                prop_assert_eq!(&map.to, &format!("m{}", i + 1));
+
+                let parsed: Vec<(u64, u64, u64)> =
+                    map.ranges.iter().map(|r| (r.destination, r.source, r.count)).collect();
+                let expected: Vec<(u64, u64, u64)> =
+                    ranges.iter().map(|&(d, s, c)| (d as u64, s as u64, c as u64)).collect();
+                prop_assert_eq!(parsed, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use common::{
+    error::{self, Context, Result},
+    generate::Rng,
+    solution::Solution,
+};
+
+/// Races as (time, record distance), read as separate numbers and with the
+/// spaces between digits ignored.
+pub struct Input {
+    pub races: Vec<(u64, u64)>,
+    pub race: Vec<(u64, u64)>,
+}
+
+/// Day 6: Wait For It.
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input {
+            races: read_races(input, parse_numbers)?,
+            race: read_races(input, parse_numbers2)?,
+        })
+    }
+
+    /// Races lasting up to `size` milliseconds, each record can be beaten.
+    /// There are as many races as fit in 64 bits once part 2 joins their
+    /// numbers, which is a single one for times above 10^9.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let max_time = (size as u64).clamp(2, 4_000_000_000);
+        let (mut times, mut distances) = (Vec::new(), Vec::new());
+
+        loop {
+            let time = rng.below(max_time - 1) + 2;
+            // The best hold time covers half of the race.
+            let distance = rng.below(time / 2 * (time - time / 2));
+
+            let digits = |ns: &[u64], n: u64| -> usize {
+                ns.iter().chain([&n]).map(|n| n.to_string().len()).sum()
+            };
+            if !times.is_empty() && (digits(&times, time) > 19 || digits(&distances, distance) > 19)
+            {
+                break;
+            }
+            times.push(time);
+            distances.push(distance);
+        }
+
+        let width = distances
+            .iter()
+            .map(|d| d.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let row = |ns: &[u64]| -> String {
+            ns.iter()
+                .map(|n| format!(" {:>w$}", n, w = width + 1))
+                .collect()
+        };
+        format!("Time:    {}\nDistance:{}\n", row(&times), row(&distances))
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        Ok(solve(&input.races))
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        Ok(solve(&input.race))
+    }
+}
+
+fn read_races(input: &str, parse_nums: fn(&str) -> Result<Vec<u64>>) -> Result<Vec<(u64, u64)>> {
+    let mut times: Vec<u64> = Vec::new();
+    let mut distances: Vec<u64> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        if let Some(s) = line.strip_prefix("Time: ") {
+            times = parse_nums(s).at_line(i, line)?;
+        } else if let Some(s) = line.strip_prefix("Distance: ") {
+            distances = parse_nums(s).at_line(i, line)?;
+        }
+    }
+
+    Ok(times.into_iter().zip(distances).collect())
+}
+
+/// Product of the numbers of ways to beat the record of each (time, distance)
+/// race.
+pub fn solve(races: &[(u64, u64)]) -> u64 {
+    let mut res: u64 = 1;
+
+    for &(t, d) in races {
+        let eq = SquareEq::new(1.0, -(t as f64), d as f64);
+        if let Some(roots) = eq.roots() {
+            let mut low: u64 = roots.0.ceil() as u64;
+            let mut high: u64 = roots.1.floor() as u64;
+            if low as f64 == roots.0 {
+                low += 1;
+            }
+            if high as f64 == roots.1 {
+                high -= 1;
+            }
+            res *= high - low + 1;
+        }
+    }
+
+    res
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<u64>> {
+    let mut nums: Vec<u64> = Vec::new();
+
+    for ns in s.split_whitespace() {
+        nums.push(error::parse(ns, "number")?)
+    }
+
+    Ok(nums)
+}
+
+fn parse_numbers2(s: &str) -> Result<Vec<u64>> {
+    Ok(vec![error::parse(&s.replace(' ', ""), "number")?])
+}
+
+#[derive(Debug)]
+struct SquareEq {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl SquareEq {
+    fn new(a: f64, b: f64, c: f64) -> SquareEq {
+        SquareEq { a, b, c }
+    }
+
+    fn discriminant(&self) -> f64 {
+        self.b.powi(2) - 4.0 * self.a * self.c
+    }
+
+    fn roots(&self) -> Option<(f64, f64)> {
+        let d = self.discriminant();
+        if d < 0.0 {
+            return None;
+        }
+
+        let r1 = (-self.b - d.sqrt()) / 2.0 * self.a;
+        let r2 = (-self.b + d.sqrt()) / 2.0 * self.a;
+
+        Some((r1, r2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day6::solve1(EXAMPLE), Ok(288));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day6::solve2(EXAMPLE), Ok(71503));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day6>(100);
+        testing::stress::<Day6>(1_000_000_000);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day6::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(races in prop::collection::vec((0..10000_u64, 0..10000_u64), 1..5)) {
+            let (times, distances): (Vec<u64>, Vec<u64>) = races.iter().copied().unzip();
+            let row = |nums: &[u64]| nums.iter().map(|n| format!("{:>5}", n)).collect::<String>();
+            let text = format!("Time: {}\nDistance: {}\n", row(&times), row(&distances));
+
+            let input = Day6::parse(&text).unwrap();
+
+            prop_assert_eq!(&input.races, &races);
+
+            let join = |nums: &[u64]| nums.iter().map(u64::to_string).collect::<String>().parse::<u64>().unwrap();
+            prop_assert_eq!(&input.race, &vec![(join(&times), join(&distances))]);
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use common::{
+    error::{self, Error, Result},
+    generate::Rng,
+    solution::Solution,
+    stream::{self, Streaming},
+};
+use itertools::Itertools;
+use std::{collections::BTreeMap, io::BufRead, str::FromStr};
+
+/// Hands with their bids.
+pub struct Input(pub Vec<Row>);
+
+/// Day 7: Camel Cards.
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(
+            stream::lines::<Self, _>(input.as_bytes()).collect::<Result<_>>()?,
+        ))
+    }
+
+    /// `size` random hands with bids up to 1000.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const CARDS: [char; 13] = [
+            '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+        ];
+
+        let mut res = String::new();
+        for _ in 0..size {
+            let hand: String = (0..5).map(|_| *rng.pick(&CARDS)).collect();
+            res += &format!("{} {}\n", hand, rng.usize(1..1001));
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<u64> {
+        Ok(winnings(&input.0, false))
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        Ok(winnings(&input.0, true))
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Result<(u64, u64)> {
+        stream::solve::<Self>(reader)
+    }
+}
+
+impl Streaming for Day7 {
+    type Line = Row;
+    /// Standings without and with jokers.
+    type State = (Standings, Standings);
+
+    fn parse_line(line: &str) -> Result<Row> {
+        line.parse()
+    }
+
+    fn push(standings: &mut (Standings, Standings), row: &Row) -> Result<()> {
+        standings.0.add(row.hand.get_combination(false), row.bid);
+        standings.1.add(row.hand.get_combination(true), row.bid);
+        Ok(())
+    }
+
+    fn answers(standings: &(Standings, Standings)) -> (u64, u64) {
+        (standings.0.winnings(), standings.1.winnings())
+    }
+}
+
+/// Total winnings, each bid multiplied by the rank of its hand. With
+/// `jockers` set "J" cards are jokers.
+pub fn winnings(rows: &[Row], jockers: bool) -> u64 {
+    let mut rows: Vec<&Row> = rows.iter().collect();
+
+    rows.sort_by_key(|r| r.hand.get_combination(jockers));
+
+    let mut res: u64 = 0;
+    for (i, row) in rows.iter().enumerate() {
+        let rank = (i + 1) as u64;
+        res += u64::from(row.bid) * rank;
+    }
+
+    res
+}
+
+/// Bids grouped by the combination of their hand, which ranks hands without
+/// keeping them. There are at most 13^5 distinct hands, so the memory stays
+/// bounded however many rows are added.
+#[derive(Debug, Default)]
+pub struct Standings(BTreeMap<Combination, Bids>);
+
+/// Bids of equal hands.
+#[derive(Debug, Default)]
+struct Bids {
+    count: u64,
+    sum: u64,
+    /// Sum of the bids multiplied by their position among the equal hands.
+    tied: u64,
+}
+
+impl Standings {
+    pub fn add(&mut self, combination: Combination, bid: u32) {
+        let bids = self.0.entry(combination).or_default();
+        bids.tied += u64::from(bid) * bids.count;
+        bids.count += 1;
+        bids.sum += u64::from(bid);
+    }
+
+    /// Total winnings like [`winnings`], equal hands rank in the order they
+    /// were added.
+    pub fn winnings(&self) -> u64 {
+        let mut weaker: u64 = 0;
+        let mut res: u64 = 0;
+        for bids in self.0.values() {
+            res += bids.sum * (weaker + 1) + bids.tied;
+            weaker += bids.count;
+        }
+        res
+    }
+}
+
+/// Hand with its bid.
+#[derive(Debug)]
+pub struct Row {
+    pub hand: Hand,
+    pub bid: u32,
+}
+
+impl FromStr for Row {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (hand_str, bid_str) = s
+            .split_once(' ')
+            .ok_or_else(|| Error::new("expected \"<hand> <bid>\"").with_text(s))?;
+
+        let hand: Hand = hand_str.parse()?;
+        let bid: u32 = error::parse(bid_str, "bid")?;
+
+        Ok(Row { hand, bid })
+    }
+}
+
+/// Hand type with the cards to break ties, ordered from the weakest.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Combination {
+    Nothing((Card, Card, Card, Card, Card)),
+    Pair((Card, Card, Card, Card, Card)),
+    TwoPairs((Card, Card, Card, Card, Card)),
+    Three((Card, Card, Card, Card, Card)),
+    FullHouse((Card, Card, Card, Card, Card)),
+    Four((Card, Card, Card, Card, Card)),
+    Five((Card, Card, Card, Card, Card)),
+}
+
+/// Five cards in the order they were dealt.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Hand {
+    pub cards: [Card; 5],
+}
+
+impl Hand {
+    /// Returns hand combination, with `jockers` set "J" cards are wildcards
+    /// that are weaker than any other card.
+    pub fn get_combination(&self, jockers: bool) -> Combination {
+        let hand_cards = if jockers {
+            self.cards
+                .map(|c| if c == Card::Jack { Card::Jocker } else { c })
+        } else {
+            self.cards
+        };
+
+        let mut cards = hand_cards
+            .into_iter()
+            .filter(|c| *c != Card::Jocker)
+            .collect::<Vec<Card>>();
+
+        cards.sort();
+
+        let mut prev: Option<Card> = None;
+        let mut count: u32 = 1;
+        let mut combs: Vec<(Card, u32)> = Vec::new();
+        let jockers_count: u32 = 5 - cards.len() as u32;
+
+        for card in cards {
+            if let Some(prev) = prev {
+                if prev == card {
+                    count += 1;
+                } else {
+                    combs.push((prev, count));
+                    count = 1;
+                }
+            }
+            prev = Some(card);
+        }
+        if let Some(prev) = prev {
+            combs.push((prev, count));
+        }
+
+        combs.sort_by_key(|c| c.1);
+
+        if let Some(last) = combs.last_mut() {
+            last.1 += jockers_count;
+        } else {
+            combs.push((Card::Ace, 5));
+        }
+
+        let orig_cards = hand_cards.into_iter().collect_tuple().unwrap();
+
+        match combs[..] {
+            [(_, 1), (_, 1), (_, 1), (_, 1), (_, 1)] => Combination::Nothing(orig_cards),
+            [(_, 1), (_, 1), (_, 1), (_, 2)] => Combination::Pair(orig_cards),
+            [(_, 1), (_, 2), (_, 2)] => Combination::TwoPairs(orig_cards),
+            [(_, 1), (_, 1), (_, 3)] => Combination::Three(orig_cards),
+            [(_, 2), (_, 3)] => Combination::FullHouse(orig_cards),
+            [(_, 1), (_, 4)] => Combination::Four(orig_cards),
+            [(_, 5)] => Combination::Five(orig_cards),
+            _ => panic!("invalid combination"),
+        }
+    }
+}
+
+impl FromStr for Hand {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.chars().count() != 5 {
+            return Err(Error::new("expected 5 cards").with_text(s));
+        }
+
+        let mut cards: [Card; 5] = [Card::Two; 5];
+
+        for (i, (j, c)) in s.char_indices().enumerate() {
+            let card: Card = s[j..j + c.len_utf8()].parse()?;
+            cards[i] = card;
+        }
+
+        Ok(Hand { cards })
+    }
+}
+
+/// Card ordered by strength, `Jocker` is "J" when it is a joker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Card {
+    Jocker,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl FromStr for Card {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "2" => Ok(Card::Two),
+            "3" => Ok(Card::Three),
+            "4" => Ok(Card::Four),
+            "5" => Ok(Card::Five),
+            "6" => Ok(Card::Six),
+            "7" => Ok(Card::Seven),
+            "8" => Ok(Card::Eight),
+            "9" => Ok(Card::Nine),
+            "T" => Ok(Card::Ten),
+            "J" => Ok(Card::Jack),
+            "Q" => Ok(Card::Queen),
+            "K" => Ok(Card::King),
+            "A" => Ok(Card::Ace),
+            v => Err(Error::invalid("card", v)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day7::solve1(EXAMPLE), Ok(6440));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day7::solve2(EXAMPLE), Ok(5905));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day7>(1000);
+    }
+
+    #[test]
+    fn test_stream() {
+        testing::stream::<Day7>(1000);
+
+        // Equal hands rank in the order of the input.
+        let input = "KKKKK 5\n22222 1\n22222 2\nKKKKK 7\n";
+        assert_eq!(Day7::solve1(input), Ok(1 + 2 * 2 + 5 * 3 + 7 * 4));
+        assert_eq!(
+            stream::solve::<Day7>(input.as_bytes()),
+            Ok((1 + 2 * 2 + 5 * 3 + 7 * 4, 1 + 2 * 2 + 5 * 3 + 7 * 4))
+        );
+    }
+
+    /// Cards from the weakest to the strongest, in the order of `Card`
+    /// variants after `Jocker`.
+    const CARDS: &str = "23456789TJQKA";
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day7::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(rows in prop::collection::vec(("[2-9TJQKA]{5}", any::<u32>()), 0..10)) {
+            let lines: Vec<String> = rows.iter().map(|(hand, bid)| format!("{} {}", hand, bid)).collect();
+
+            let input = Day7::parse(&testing::lines(&lines)).unwrap();
+
+            prop_assert_eq!(input.0.len(), rows.len());
+            for (row, (hand, bid)) in input.0.iter().zip(&rows) {
+                let cards: String = row.hand.cards.iter().map(|&c| CARDS.as_bytes()[c as usize - 1] as char).collect();
+                prop_assert_eq!(&cards, hand);
+                prop_assert_eq!(row.bid, *bid);
+            }
+        }
+    }
+}
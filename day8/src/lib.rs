@@ -0,0 +1,297 @@
+use common::{
+    error::{Context, Error, Result},
+    generate::Rng,
+    graph::Graph,
+    math,
+    solution::Solution,
+};
+use std::collections::HashMap;
+
+/// Network of nodes and the commands to walk it.
+pub struct Input {
+    pub map: Network,
+    pub commands: Vec<Command>,
+}
+
+/// Day 8: Haunted Wasteland.
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Input> {
+        let (map, commands) = read_input(input)?;
+        Ok(Input { map, commands })
+    }
+
+    /// `size` commands and 6 ghosts, each walking a loop from its start node
+    /// to its end node and back to the node after the start. Loop lengths are
+    /// `size` times distinct primes like in the puzzle input, so part 2 fits
+    /// in 64 bits. The first ghost starts at "AAA" and ends at "ZZZ".
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const PRIMES: [usize; 10] = [43, 47, 53, 59, 61, 67, 71, 73, 79, 83];
+        const LAST: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY0123456789";
+        let size = size.max(1);
+
+        // Base 36 digits and a last character which is neither "A" nor "Z".
+        let mut next_id = 0;
+        let mut middle = || {
+            let (mut head, mut n) = (String::new(), next_id / LAST.len());
+            while head.len() < 2 || n > 0 {
+                let digit = char::from_digit((n % 36) as u32, 36).unwrap();
+                head.insert(0, digit.to_ascii_uppercase());
+                n /= 36;
+            }
+            head.push(LAST[next_id % LAST.len()] as char);
+            next_id += 1;
+            head
+        };
+
+        let mut primes = PRIMES;
+        rng.shuffle(&mut primes);
+
+        let mut lines = Vec::new();
+        for (ghost, prime) in primes[..6].iter().enumerate() {
+            let (start, end) = match ghost {
+                0 => ("AAA".to_string(), "ZZZ".to_string()),
+                _ => (format!("{0}{0}A", ghost), format!("{0}{0}Z", ghost)),
+            };
+            let mut nodes = vec![start];
+            nodes.extend((1..size * prime).map(|_| middle()));
+            nodes.push(end);
+            // The end node goes back to the node after the start.
+            nodes.push(nodes[1].clone());
+
+            for pair in nodes.windows(2) {
+                lines.push(format!("{0} = ({1}, {1})", pair[0], pair[1]));
+            }
+        }
+        rng.shuffle(&mut lines);
+
+        let commands: String = (0..size)
+            .map(|_| if rng.chance(0.5) { 'L' } else { 'R' })
+            .collect();
+        format!("{}\n\n{}\n", commands, lines.join("\n"))
+    }
+
+    fn part1(input: &Input) -> Result<u32> {
+        steps(&input.map, &input.commands)
+    }
+
+    fn part2(input: &Input) -> Result<u64> {
+        ghost_steps(&input.map, &input.commands)
+    }
+
+    fn graph(input: &Input) -> Result<Graph> {
+        Ok(graph(&input.map))
+    }
+}
+
+/// Left and right neighbours of each node.
+pub type Network = HashMap<String, (String, String)>;
+
+fn read_input(input: &str) -> Result<(Network, Vec<Command>)> {
+    let mut commands: Vec<Command> = Vec::new();
+    let mut map: HashMap<String, (String, String)> = HashMap::new();
+
+    for (i, line) in input.lines().enumerate() {
+        if i == 0 {
+            commands = parse_commands(line).at_line(i, line)?;
+        } else if i > 1 {
+            let (key, value) = parse_line(line).at_line(i, line)?;
+            map.insert(key, value);
+        }
+    }
+
+    if commands.is_empty() {
+        return Err(Error::new("empty commands"));
+    }
+
+    Ok((map, commands))
+}
+
+/// Steps from "AAA" to "ZZZ" following the commands.
+pub fn steps(map: &Network, commands: &[Command]) -> Result<u32> {
+    let mut key: String = "AAA".to_owned();
+    let mut cmd_index: usize = 0;
+    let mut steps: u32 = 0;
+
+    while key != "ZZZ" {
+        let cmd = &commands[cmd_index % commands.len()];
+        let values = map
+            .get(&key)
+            .ok_or_else(|| Error::new(format!("unknown node {}", key)))?;
+        match cmd {
+            Command::Left => key = values.0.to_owned(),
+            Command::Right => key = values.1.to_owned(),
+        }
+        cmd_index += 1;
+        steps += 1;
+    }
+
+    Ok(steps)
+}
+
+/// Steps until all ghosts starting at "..A" nodes are at "..Z" nodes at once.
+pub fn ghost_steps(map: &Network, commands: &[Command]) -> Result<u64> {
+    let keys: Vec<String> = map
+        .clone()
+        .into_keys()
+        .filter(|key| key.ends_with("A"))
+        .collect();
+
+    let mut steps: Vec<u64> = vec![0; keys.len()];
+
+    for (i, key) in keys.iter().enumerate() {
+        // Every ghost starts from the first command.
+        let mut cmd_index: usize = 0;
+        let mut key: String = key.to_owned();
+        let step = &mut steps[i];
+        while !key.ends_with("Z") {
+            let cmd = &commands[cmd_index % commands.len()];
+            let values = map
+                .get(&key)
+                .ok_or_else(|| Error::new(format!("unknown node {}", key)))?;
+            match cmd {
+                Command::Left => key = values.0.to_owned(),
+                Command::Right => key = values.1.to_owned(),
+            }
+            cmd_index += 1;
+            *step += 1;
+        }
+    }
+
+    match math::lcm_all(steps) {
+        Some(res) => Ok(res),
+        None if keys.is_empty() => Err(Error::new("no starting nodes")),
+        None => Err(Error::new("number of steps overflows u64")),
+    }
+}
+
+fn parse_line(s: &str) -> Result<(String, (String, String))> {
+    let (key, rest) = s
+        .split_once(" = ")
+        .ok_or_else(|| Error::new("expected \"<node> = (<left>, <right>)\"").with_text(s))?;
+
+    let ss: &str = rest
+        .strip_prefix('(')
+        .and_then(|r| r.strip_suffix(')'))
+        .ok_or_else(|| Error::invalid("node pair", rest))?;
+    let (left, right) = ss
+        .split_once(", ")
+        .ok_or_else(|| Error::invalid("node pair", rest))?;
+
+    Ok((key.to_owned(), (left.to_owned(), right.to_owned())))
+}
+
+/// Network with an edge to the left and the right neighbour of each node,
+/// start nodes are boxes and end nodes double circles.
+pub fn graph(map: &Network) -> Graph {
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort();
+
+    let mut res = Graph::directed("network");
+    for key in keys {
+        if key.ends_with('A') {
+            res.node(key, &[("shape", "box")]);
+        } else if key.ends_with('Z') {
+            res.node(key, &[("shape", "doublecircle")]);
+        }
+        let (left, right) = &map[key];
+        res.edge(key, left, &[("label", "L")]);
+        res.edge(key, right, &[("label", "R")]);
+    }
+    res
+}
+
+/// Turn to take at a node.
+#[derive(Debug)]
+pub enum Command {
+    Left,
+    Right,
+}
+
+fn parse_commands(s: &str) -> Result<Vec<Command>> {
+    let mut commands = Vec::new();
+    for (i, c) in s.char_indices() {
+        match c {
+            'R' => commands.push(Command::Right),
+            'L' => commands.push(Command::Left),
+            _ => return Err(Error::invalid("command", &s[i..i + c.len_utf8()])),
+        }
+    }
+
+    Ok(commands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+    const EXAMPLE2: &str = include_str!("../files/example2.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day8::solve1(EXAMPLE), Ok(2));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day8::solve2(EXAMPLE2), Ok(6));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day8>(20);
+    }
+
+    #[test]
+    fn test_graph() {
+        let graph = Day8::graph(&Day8::parse(EXAMPLE).unwrap()).unwrap();
+
+        assert_eq!(graph.node_count(), 7);
+        assert_eq!(graph.edge_count(), 14);
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph \"network\" {\n  \"AAA\" [shape=\"box\"];\n"));
+        assert!(dot.contains("  \"AAA\" -> \"BBB\" [label=\"L\"];\n"));
+        assert!(dot.contains("  \"AAA\" -> \"CCC\" [label=\"R\"];\n"));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day8::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(
+            commands in "[LR]{1,10}",
+            nodes in prop::collection::btree_map("[A-Z0-9]{3}", ("[A-Z0-9]{3}", "[A-Z0-9]{3}"), 0..10),
+        ) {
+            let mut text = format!("{}\n\n", commands);
+            for (node, (left, right)) in &nodes {
+                text += &format!("{} = ({}, {})\n", node, left, right);
+            }
+
+            let input = Day8::parse(&text).unwrap();
+
+            let parsed: String = input
+                .commands
+                .iter()
+                .map(|c| match c {
+                    Command::Left => 'L',
+                    Command::Right => 'R',
+                })
+                .collect();
+            prop_assert_eq!(parsed, commands);
+            prop_assert_eq!(input.map, nodes.into_iter().collect::<Network>());
+        }
+    }
+}
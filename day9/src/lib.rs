@@ -0,0 +1,188 @@
+use std::io::BufRead;
+
+use common::{
+    error::{self, Result},
+    generate::Rng,
+    solution::Solution,
+    stream::{self, Streaming},
+};
+
+/// Sequences of the report, one per line.
+pub struct Input(pub Vec<Vec<i32>>);
+
+/// Day 9: Mirage Maintenance.
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Input;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Input> {
+        Ok(Input(
+            stream::lines::<Self, _>(input.as_bytes()).collect::<Result<_>>()?,
+        ))
+    }
+
+    /// `size` sequences of 21 values of polynomials of degree up to 5.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut res = String::new();
+        for _ in 0..size {
+            // Newton form: value at x is the sum of c_k * C(x, k).
+            let coefficients: Vec<i64> = (0..rng.usize(1..7)).map(|_| rng.i64(-5..6)).collect();
+            let values: Vec<String> = (0..21)
+                .map(|x| {
+                    let mut binomial = 1;
+                    let mut value = 0;
+                    for (k, c) in coefficients.iter().enumerate() {
+                        value += c * binomial;
+                        binomial = binomial * (x - k as i64) / (k as i64 + 1);
+                    }
+                    value.to_string()
+                })
+                .collect();
+            res += &values.join(" ");
+            res.push('\n');
+        }
+        res
+    }
+
+    fn part1(input: &Input) -> Result<i64> {
+        Ok(input.0.iter().map(|nums| i64::from(ext(nums, next))).sum())
+    }
+
+    fn part2(input: &Input) -> Result<i64> {
+        Ok(input.0.iter().map(|nums| i64::from(ext(nums, prev))).sum())
+    }
+
+    fn stream(reader: &mut dyn BufRead) -> Result<(i64, i64)> {
+        stream::solve::<Self>(reader)
+    }
+}
+
+impl Streaming for Day9 {
+    type Line = Vec<i32>;
+    /// Sums of next and previous values.
+    type State = (i64, i64);
+
+    fn parse_line(line: &str) -> Result<Vec<i32>> {
+        parse_line(line)
+    }
+
+    fn push(sums: &mut (i64, i64), nums: &Vec<i32>) -> Result<()> {
+        sums.0 += i64::from(ext(nums, next));
+        sums.1 += i64::from(ext(nums, prev));
+        Ok(())
+    }
+
+    fn answers(sums: &(i64, i64)) -> (i64, i64) {
+        *sums
+    }
+}
+
+/// Extrapolates the sequence with `f` applied to it and all its differences.
+pub fn ext(nums: &[i32], f: fn(&[Vec<i32>]) -> i32) -> i32 {
+    let mut nums = nums.to_vec();
+    let mut vecs: Vec<Vec<i32>> = vec![nums.clone()];
+
+    while !nums.iter().all(|x| *x == 0) {
+        nums = nums.windows(2).map(|x| x[1] - x[0]).collect::<Vec<i32>>();
+        vecs.push(nums.clone());
+    }
+
+    f(&vecs)
+}
+
+/// Next value of the first sequence, given all its differences.
+pub fn next(vecs: &[Vec<i32>]) -> i32 {
+    let mut n: i32 = 0;
+    for v in vecs.iter().rev().skip(1) {
+        n += v.last().unwrap();
+    }
+    n
+}
+
+/// Previous value of the first sequence, given all its differences.
+pub fn prev(vecs: &[Vec<i32>]) -> i32 {
+    let mut n: i32 = 0;
+    for v in vecs.iter().rev().skip(1) {
+        n = v.first().unwrap() - n;
+    }
+    n
+}
+
+fn parse_line(s: &str) -> Result<Vec<i32>> {
+    let mut nums: Vec<i32> = Vec::new();
+
+    for ns in s.split_whitespace() {
+        nums.push(error::parse(ns, "number")?);
+    }
+
+    Ok(nums)
+}
+
+#[cfg(test)]
+mod tests {
+    // Note this useful idiom: importing names from outer (for mod tests) scope.
+    use super::*;
+    use common::testing;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("../files/example.txt");
+
+    #[test]
+    fn test_parse_nums() {
+        let s: &str = "1 2 3 4";
+        assert_eq!(parse_line(s), Ok(vec![1, 2, 3, 4]));
+
+        let s: &str = "1 -2 3 -4";
+        assert_eq!(parse_line(s), Ok(vec![1, -2, 3, -4]));
+    }
+
+    #[test]
+    fn test_next() {
+        assert_eq!(ext(&[1, 2, 3, 4], next), 5);
+    }
+
+    #[test]
+    fn test_prev() {
+        assert_eq!(ext(&[1, 2, 3, 4], prev), 0);
+        assert_eq!(ext(&[1, 4, 7, 10], prev), -2);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day9::solve1(EXAMPLE), Ok(114));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day9::solve2(EXAMPLE), Ok(2));
+    }
+
+    #[test]
+    fn test_stress() {
+        testing::stress::<Day9>(2000);
+    }
+
+    #[test]
+    fn test_stream() {
+        testing::stream::<Day9>(2000);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_no_panic(s in testing::input(EXAMPLE)) {
+            let _ = Day9::parse(&s);
+        }
+
+        #[test]
+        fn test_parse_round_trip(seqs in prop::collection::vec(prop::collection::vec(any::<i32>(), 1..10), 0..5)) {
+            let lines: Vec<String> = seqs.iter().map(|seq| seq.iter().map(i32::to_string).collect::<Vec<_>>().join(" ")).collect();
+
+            prop_assert_eq!(Day9::parse(&testing::lines(&lines)).unwrap().0, seqs);
+        }
+    }
+}
@@ -0,0 +1,498 @@
+//! Two-dimensional grid of cells shared by the map based puzzles.
+
+use common::{
+    error::{Error, Result},
+    render::Canvas,
+};
+use std::{
+    fmt::Display,
+    iter::StepBy,
+    ops::{Index, IndexMut},
+    slice,
+    str::FromStr,
+};
+
+/// Position of a cell in a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Self {
+        Pos { row, col }
+    }
+
+    pub fn manhattan(&self, other: &Pos) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, col): (usize, usize)) -> Self {
+        Pos { row, col }
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(pos: Pos) -> Self {
+        (pos.row, pos.col)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn turn_left(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Right => Direction::Down,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// Character pointing in the direction, as used on puzzle maps.
+    pub fn arrow(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+
+    /// Row and column deltas of a single step.
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+/// Deltas of all eight neighbours, clockwise starting from the upper left one.
+const DELTAS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+];
+
+/// Rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let cols = rows
+            .first()
+            .map(Vec::len)
+            .filter(|&cols| cols > 0)
+            .ok_or("empty grid")?;
+        let n = rows.len();
+        let mut data = Vec::with_capacity(n * cols);
+
+        for (i, row) in rows.into_iter().enumerate() {
+            if row.len() != cols {
+                return Err(Error::new(format!(
+                    "row {} has length {}, expected {}",
+                    i + 1,
+                    row.len(),
+                    cols
+                )));
+            }
+            data.extend(row);
+        }
+
+        Ok(Grid {
+            data,
+            rows: n,
+            cols,
+        })
+    }
+
+    /// Parses grid from text, one row per line, converting every character
+    /// with `f`.
+    pub fn parse_with<F>(s: &str, mut f: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let row = line
+                .char_indices()
+                .map(|(j, ch)| {
+                    f(ch).ok_or_else(|| {
+                        Error::invalid("cell", &line[j..j + ch.len_utf8()]).at_line(i, line)
+                    })
+                })
+                .collect::<Result<Vec<T>>>()?;
+
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    return Err(Error::new(format!(
+                        "row has length {}, expected {}",
+                        row.len(),
+                        first.len()
+                    ))
+                    .at_line(i, line));
+                }
+            }
+
+            rows.push(row);
+        }
+
+        Self::from_rows(rows)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.rows && pos.col < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.data[pos.row * self.cols + pos.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.data[pos.row * self.cols + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /// Position shifted by the given deltas, `None` if it's outside of the grid.
+    pub fn offset(&self, pos: Pos, drow: isize, dcol: isize) -> Option<Pos> {
+        let row = pos.row.checked_add_signed(drow)?;
+        let col = pos.col.checked_add_signed(dcol)?;
+        let pos = Pos::new(row, col);
+
+        if self.contains(pos) {
+            Some(pos)
+        } else {
+            None
+        }
+    }
+
+    /// Adjacent position in the given direction, `None` at the border.
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        let (drow, dcol) = direction.delta();
+        self.offset(pos, drow, dcol)
+    }
+
+    /// Orthogonal neighbours of a cell which are inside of the grid.
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Orthogonal and diagonal neighbours of a cell which are inside of the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DELTAS8
+            .into_iter()
+            .filter_map(move |(drow, dcol)| self.offset(pos, drow, dcol))
+    }
+
+    pub fn row(&self, index: usize) -> &[T] {
+        &self.data[index * self.cols..(index + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, index: usize) -> &mut [T] {
+        &mut self.data[index * self.cols..(index + 1) * self.cols]
+    }
+
+    pub fn col(&self, index: usize) -> StepBy<slice::Iter<'_, T>> {
+        self.data[index..].iter().step_by(self.cols)
+    }
+
+    pub fn col_mut(&mut self, index: usize) -> StepBy<slice::IterMut<'_, T>> {
+        self.data[index..].iter_mut().step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> slice::Chunks<'_, T> {
+        self.data.chunks(self.cols)
+    }
+
+    pub fn iter_cols(&self) -> impl Iterator<Item = StepBy<slice::Iter<'_, T>>> + '_ {
+        (0..self.cols).map(|i| self.col(i))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| Pos::new(i / cols, i % cols))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    /// Position of the first cell, in row-major order, satisfying the predicate.
+    pub fn position<P>(&self, predicate: P) -> Option<Pos>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.data
+            .iter()
+            .position(predicate)
+            .map(|i| Pos::new(i / self.cols, i % self.cols))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self {
+        Grid {
+            data: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Grid with rows and columns swapped.
+    pub fn transpose(&self) -> Self {
+        Grid {
+            data: self.iter_cols().flatten().cloned().collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Grid rotated by 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        Grid {
+            data: self.iter_cols().flat_map(|c| c.rev()).cloned().collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    /// Grid rotated by 90 degrees counterclockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        Grid {
+            data: (0..self.cols)
+                .rev()
+                .flat_map(|i| self.col(i))
+                .cloned()
+                .collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        assert!(self.contains(pos), "{:?} is out of grid", pos);
+        &self.data[pos.row * self.cols + pos.col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        assert!(self.contains(pos), "{:?} is out of grid", pos);
+        &mut self.data[pos.row * self.cols + pos.col]
+    }
+}
+
+/// Parses grid with every character converted to a cell with `TryFrom<char>`.
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_with(s, |c| T::try_from(c).ok())
+    }
+}
+
+impl<T: Display> Grid<T> {
+    /// Canvas with a cell per grid cell, showing the first character of the
+    /// cell's text.
+    pub fn canvas(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.rows, self.cols, ' ');
+        for (pos, cell) in self.iter() {
+            if let Some(ch) = cell.to_string().chars().next() {
+                canvas.set(pos, ch);
+            }
+        }
+        canvas
+    }
+}
+
+/// Renders grid back to text, one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::render::{Format, Style};
+
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = grid();
+
+        assert_eq!(g.rows(), 2);
+        assert_eq!(g.cols(), 3);
+        assert_eq!(g[Pos::new(1, 2)], 'f');
+        assert_eq!(g.get(Pos::new(2, 0)), None);
+        assert_eq!(g.to_string(), "abc\ndef\n");
+
+        assert_eq!(
+            "".parse::<Grid<char>>().unwrap_err().message(),
+            "empty grid"
+        );
+        assert_eq!(
+            "\n".parse::<Grid<char>>().unwrap_err().message(),
+            "empty grid"
+        );
+
+        let err = "ab\nc".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(err.message(), "row has length 1, expected 2");
+        assert_eq!(err.location().unwrap().line, 2);
+
+        let err = Grid::parse_with("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(err.message(), "invalid cell");
+        assert_eq!(err.location().unwrap().line, 2);
+        assert_eq!(err.location().unwrap().column, 2);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = grid();
+
+        assert_eq!(
+            g.neighbours(Pos::new(0, 0)).collect::<Vec<_>>(),
+            vec![Pos::new(1, 0), Pos::new(0, 1)]
+        );
+        assert_eq!(g.neighbours(Pos::new(1, 1)).count(), 3);
+        assert_eq!(g.neighbours8(Pos::new(1, 1)).count(), 5);
+        assert_eq!(g.step(Pos::new(0, 2), Direction::Right), None);
+        assert_eq!(
+            g.step(Pos::new(0, 2), Direction::Down),
+            Some(Pos::new(1, 2))
+        );
+    }
+
+    #[test]
+    fn test_views() {
+        let mut g = grid();
+
+        assert_eq!(g.row(1), &['d', 'e', 'f']);
+        assert_eq!(g.col(1).collect::<String>(), "be");
+        assert_eq!(g.col(2).rev().collect::<String>(), "fc");
+        assert_eq!(g.position(|&c| c == 'e'), Some(Pos::new(1, 1)));
+
+        g.col_mut(0).for_each(|c| *c = '#');
+        assert_eq!(g.to_string(), "#bc\n#ef\n");
+    }
+
+    #[test]
+    fn test_transform() {
+        let g = grid();
+
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+    }
+
+    #[test]
+    fn test_canvas() {
+        let g = Grid::parse_with("12\n34\n", |c| c.to_digit(10)).unwrap();
+        let mut canvas = g.canvas();
+        canvas.mark(Pos::new(1, 0), Style::Path);
+
+        assert_eq!(canvas.render(Format::Ascii), "12\n34\n");
+        assert_eq!(canvas.style((1, 0)), Some(Style::Path));
+    }
+}